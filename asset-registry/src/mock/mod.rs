@@ -262,6 +262,7 @@ pub fn para_ext(para_id: u32, asset_data: Option<(Vec<(u32, Vec<u8>)>, u32)>) ->
 
 	orml_tokens::GenesisConfig::<Runtime> {
 		balances: vec![(ALICE, CurrencyId::R, 1_000)],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -0,0 +1,113 @@
+//! Benchmarks for the currencies module.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Saturating;
+
+const SEED: u32 = 0;
+
+/// Fund `who` with a hundred times the existential deposit of `currency_id`,
+/// or a thousand units if there is none.
+fn fund<T: Config>(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+	let amount = <Pallet<T> as fungibles::Inspect<T::AccountId>>::minimum_balance(currency_id)
+		.saturating_mul(100u32.into())
+		.max(1_000u32.into());
+	assert!(<Pallet<T> as fungibles::Mutate<T::AccountId>>::mint_into(currency_id, who, amount).is_ok());
+	amount
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn transfer_all_non_native_currency() {
+		let currency_id = T::GetBenchmarkCurrencyId::get();
+		let from: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, SEED);
+		let amount = fund::<T>(currency_id, &from);
+
+		#[extrinsic_call]
+		transfer_all(
+			RawOrigin::Signed(from.clone()),
+			T::Lookup::unlookup(to.clone()),
+			currency_id,
+			false,
+		);
+
+		assert_eq!(
+			<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &from),
+			Zero::zero()
+		);
+		assert_eq!(<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &to), amount);
+	}
+
+	#[benchmark]
+	fn transfer_all_native_currency() {
+		let currency_id = T::GetNativeCurrencyId::get();
+		let from: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, SEED);
+		let amount = fund::<T>(currency_id, &from);
+
+		#[extrinsic_call]
+		transfer_all(
+			RawOrigin::Signed(from.clone()),
+			T::Lookup::unlookup(to.clone()),
+			currency_id,
+			false,
+		);
+
+		assert_eq!(
+			<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &from),
+			Zero::zero()
+		);
+		assert_eq!(<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &to), amount);
+	}
+
+	#[benchmark]
+	fn transfer_keep_alive_non_native_currency() {
+		let currency_id = T::GetBenchmarkCurrencyId::get();
+		let from: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, SEED);
+		let amount = fund::<T>(currency_id, &from);
+		let transfer_amount = amount / 2u32.into();
+
+		#[extrinsic_call]
+		transfer_keep_alive(
+			RawOrigin::Signed(from.clone()),
+			T::Lookup::unlookup(to.clone()),
+			currency_id,
+			transfer_amount,
+		);
+
+		assert_eq!(
+			<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &to),
+			transfer_amount
+		);
+	}
+
+	#[benchmark]
+	fn transfer_keep_alive_native_currency() {
+		let currency_id = T::GetNativeCurrencyId::get();
+		let from: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, SEED);
+		let amount = fund::<T>(currency_id, &from);
+		let transfer_amount = amount / 2u32.into();
+
+		#[extrinsic_call]
+		transfer_keep_alive(
+			RawOrigin::Signed(from.clone()),
+			T::Lookup::unlookup(to.clone()),
+			currency_id,
+			transfer_amount,
+		);
+
+		assert_eq!(
+			<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &to),
+			transfer_amount
+		);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
+}
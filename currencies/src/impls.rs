@@ -0,0 +1,465 @@
+use crate::{BasicCurrencyAdapter, Config, CurrencyIdOf, Pallet};
+use frame_support::traits::{
+	fungible, fungibles,
+	tokens::{DepositConsequence, Fortitude, Precision, Preservation, Provenance, Restriction, WithdrawConsequence},
+	Get,
+};
+use sp_runtime::{DispatchError, DispatchResult};
+
+type FungiblesBalanceOf<T> =
+	<<T as Config>::MultiCurrency as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+type FungiblesReasonOf<T> =
+	<<T as Config>::MultiCurrency as fungibles::InspectHold<<T as frame_system::Config>::AccountId>>::Reason;
+
+impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T>
+where
+	T::MultiCurrency: fungibles::Inspect<T::AccountId, AssetId = CurrencyIdOf<T>>,
+	T::NativeCurrency: fungible::Inspect<T::AccountId, Balance = FungiblesBalanceOf<T>>,
+{
+	type AssetId = <T::MultiCurrency as fungibles::Inspect<T::AccountId>>::AssetId;
+	type Balance = FungiblesBalanceOf<T>;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Inspect<_>>::total_issuance()
+		} else {
+			<T::MultiCurrency as fungibles::Inspect<_>>::total_issuance(asset)
+		}
+	}
+
+	fn minimum_balance(asset: Self::AssetId) -> Self::Balance {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Inspect<_>>::minimum_balance()
+		} else {
+			<T::MultiCurrency as fungibles::Inspect<_>>::minimum_balance(asset)
+		}
+	}
+
+	fn balance(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Inspect<_>>::balance(who)
+		} else {
+			<T::MultiCurrency as fungibles::Inspect<_>>::balance(asset, who)
+		}
+	}
+
+	fn total_balance(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Inspect<_>>::total_balance(who)
+		} else {
+			<T::MultiCurrency as fungibles::Inspect<_>>::total_balance(asset, who)
+		}
+	}
+
+	fn reducible_balance(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		preservation: Preservation,
+		fortitude: Fortitude,
+	) -> Self::Balance {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Inspect<_>>::reducible_balance(who, preservation, fortitude)
+		} else {
+			<T::MultiCurrency as fungibles::Inspect<_>>::reducible_balance(asset, who, preservation, fortitude)
+		}
+	}
+
+	fn can_deposit(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Inspect<_>>::can_deposit(who, amount, provenance)
+		} else {
+			<T::MultiCurrency as fungibles::Inspect<_>>::can_deposit(asset, who, amount, provenance)
+		}
+	}
+
+	fn can_withdraw(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Inspect<_>>::can_withdraw(who, amount)
+		} else {
+			<T::MultiCurrency as fungibles::Inspect<_>>::can_withdraw(asset, who, amount)
+		}
+	}
+
+	fn asset_exists(asset: Self::AssetId) -> bool {
+		if asset == T::GetNativeCurrencyId::get() {
+			true
+		} else {
+			<T::MultiCurrency as fungibles::Inspect<_>>::asset_exists(asset)
+		}
+	}
+}
+
+impl<T: Config> fungibles::Unbalanced<T::AccountId> for Pallet<T>
+where
+	T::MultiCurrency: fungibles::Unbalanced<T::AccountId, AssetId = CurrencyIdOf<T>>,
+	T::NativeCurrency: fungible::Unbalanced<T::AccountId, Balance = FungiblesBalanceOf<T>>,
+{
+	fn handle_dust(dust: fungibles::Dust<T::AccountId, Self>) {
+		let fungibles::Dust(asset, amount) = dust;
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Unbalanced<_>>::handle_dust(fungible::Dust(amount))
+		} else {
+			<T::MultiCurrency as fungibles::Unbalanced<_>>::handle_dust(fungibles::Dust(asset, amount))
+		}
+	}
+
+	fn write_balance(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Option<Self::Balance>, DispatchError> {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Unbalanced<_>>::write_balance(who, amount)
+		} else {
+			<T::MultiCurrency as fungibles::Unbalanced<_>>::write_balance(asset, who, amount)
+		}
+	}
+
+	fn set_total_issuance(asset: Self::AssetId, amount: Self::Balance) {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Unbalanced<_>>::set_total_issuance(amount)
+		} else {
+			<T::MultiCurrency as fungibles::Unbalanced<_>>::set_total_issuance(asset, amount)
+		}
+	}
+}
+
+impl<T: Config> fungibles::Mutate<T::AccountId> for Pallet<T>
+where
+	T::MultiCurrency: fungibles::Mutate<T::AccountId, AssetId = CurrencyIdOf<T>>,
+	T::NativeCurrency: fungible::Mutate<T::AccountId, Balance = FungiblesBalanceOf<T>>,
+{
+	fn mint_into(
+		asset: Self::AssetId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Mutate<_>>::mint_into(dest, amount)
+		} else {
+			<T::MultiCurrency as fungibles::Mutate<_>>::mint_into(asset, dest, amount)
+		}
+	}
+
+	fn burn_from(
+		asset: Self::AssetId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		fortitude: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Mutate<_>>::burn_from(dest, amount, precision, fortitude)
+		} else {
+			<T::MultiCurrency as fungibles::Mutate<_>>::burn_from(asset, dest, amount, precision, fortitude)
+		}
+	}
+
+	fn transfer(
+		asset: Self::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		preservation: Preservation,
+	) -> Result<Self::Balance, DispatchError> {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::Mutate<_>>::transfer(source, dest, amount, preservation)
+		} else {
+			<T::MultiCurrency as fungibles::Mutate<_>>::transfer(asset, source, dest, amount, preservation)
+		}
+	}
+}
+
+impl<T: Config> fungibles::InspectHold<T::AccountId> for Pallet<T>
+where
+	T::MultiCurrency: fungibles::InspectHold<T::AccountId, AssetId = CurrencyIdOf<T>>,
+	T::NativeCurrency:
+		fungible::InspectHold<T::AccountId, Balance = FungiblesBalanceOf<T>, Reason = FungiblesReasonOf<T>>,
+{
+	type Reason = FungiblesReasonOf<T>;
+
+	fn total_balance_on_hold(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::InspectHold<_>>::total_balance_on_hold(who)
+		} else {
+			<T::MultiCurrency as fungibles::InspectHold<_>>::total_balance_on_hold(asset, who)
+		}
+	}
+
+	fn reducible_total_balance_on_hold(asset: Self::AssetId, who: &T::AccountId, force: Fortitude) -> Self::Balance {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::InspectHold<_>>::reducible_total_balance_on_hold(who, force)
+		} else {
+			<T::MultiCurrency as fungibles::InspectHold<_>>::reducible_total_balance_on_hold(asset, who, force)
+		}
+	}
+
+	fn balance_on_hold(asset: Self::AssetId, reason: &Self::Reason, who: &T::AccountId) -> Self::Balance {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::InspectHold<_>>::balance_on_hold(reason, who)
+		} else {
+			<T::MultiCurrency as fungibles::InspectHold<_>>::balance_on_hold(asset, reason, who)
+		}
+	}
+
+	fn hold_available(asset: Self::AssetId, reason: &Self::Reason, who: &T::AccountId) -> bool {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::InspectHold<_>>::hold_available(reason, who)
+		} else {
+			<T::MultiCurrency as fungibles::InspectHold<_>>::hold_available(asset, reason, who)
+		}
+	}
+
+	fn can_hold(asset: Self::AssetId, reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) -> bool {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::InspectHold<_>>::can_hold(reason, who, amount)
+		} else {
+			<T::MultiCurrency as fungibles::InspectHold<_>>::can_hold(asset, reason, who, amount)
+		}
+	}
+}
+
+impl<T: Config> fungibles::UnbalancedHold<T::AccountId> for Pallet<T>
+where
+	T::MultiCurrency: fungibles::UnbalancedHold<T::AccountId, AssetId = CurrencyIdOf<T>>,
+	T::NativeCurrency:
+		fungible::UnbalancedHold<T::AccountId, Balance = FungiblesBalanceOf<T>, Reason = FungiblesReasonOf<T>>,
+{
+	fn set_balance_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::UnbalancedHold<_>>::set_balance_on_hold(reason, who, amount)
+		} else {
+			<T::MultiCurrency as fungibles::UnbalancedHold<_>>::set_balance_on_hold(asset, reason, who, amount)
+		}
+	}
+}
+
+impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T>
+where
+	T::MultiCurrency: fungibles::MutateHold<T::AccountId, AssetId = CurrencyIdOf<T>> + fungibles::Mutate<T::AccountId>,
+	T::NativeCurrency: fungible::MutateHold<T::AccountId, Balance = FungiblesBalanceOf<T>, Reason = FungiblesReasonOf<T>>
+		+ fungible::Mutate<T::AccountId>,
+{
+	fn hold(asset: Self::AssetId, reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::MutateHold<_>>::hold(reason, who, amount)
+		} else {
+			<T::MultiCurrency as fungibles::MutateHold<_>>::hold(asset, reason, who, amount)
+		}
+	}
+
+	fn release(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::MutateHold<_>>::release(reason, who, amount, precision)
+		} else {
+			<T::MultiCurrency as fungibles::MutateHold<_>>::release(asset, reason, who, amount, precision)
+		}
+	}
+
+	fn transfer_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		restriction: Restriction,
+		fortitude: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		if asset == T::GetNativeCurrencyId::get() {
+			<T::NativeCurrency as fungible::MutateHold<_>>::transfer_on_hold(
+				reason,
+				source,
+				dest,
+				amount,
+				precision,
+				restriction,
+				fortitude,
+			)
+		} else {
+			<T::MultiCurrency as fungibles::MutateHold<_>>::transfer_on_hold(
+				asset,
+				reason,
+				source,
+				dest,
+				amount,
+				precision,
+				restriction,
+				fortitude,
+			)
+		}
+	}
+}
+
+// Adapt `frame_support::traits::fungible`, so the native currency can be
+// used by the `fungibles` implementation of `Pallet`.
+impl<T, AccountId, Currency, Amount, Moment> fungible::Inspect<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::Inspect<AccountId>,
+{
+	type Balance = <Currency as fungible::Inspect<AccountId>>::Balance;
+
+	fn total_issuance() -> Self::Balance {
+		Currency::total_issuance()
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		Currency::minimum_balance()
+	}
+
+	fn balance(who: &AccountId) -> Self::Balance {
+		Currency::balance(who)
+	}
+
+	fn total_balance(who: &AccountId) -> Self::Balance {
+		Currency::total_balance(who)
+	}
+
+	fn reducible_balance(who: &AccountId, preservation: Preservation, fortitude: Fortitude) -> Self::Balance {
+		Currency::reducible_balance(who, preservation, fortitude)
+	}
+
+	fn can_deposit(who: &AccountId, amount: Self::Balance, provenance: Provenance) -> DepositConsequence {
+		Currency::can_deposit(who, amount, provenance)
+	}
+
+	fn can_withdraw(who: &AccountId, amount: Self::Balance) -> WithdrawConsequence<Self::Balance> {
+		Currency::can_withdraw(who, amount)
+	}
+}
+
+impl<T, AccountId, Currency, Amount, Moment> fungible::Unbalanced<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::Unbalanced<AccountId>,
+{
+	fn handle_dust(dust: fungible::Dust<AccountId, Self>) {
+		Currency::handle_dust(fungible::Dust(dust.0))
+	}
+
+	fn write_balance(who: &AccountId, amount: Self::Balance) -> Result<Option<Self::Balance>, DispatchError> {
+		Currency::write_balance(who, amount)
+	}
+
+	fn set_total_issuance(amount: Self::Balance) {
+		Currency::set_total_issuance(amount)
+	}
+}
+
+impl<T, AccountId, Currency, Amount, Moment> fungible::Mutate<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::Mutate<AccountId>,
+{
+	fn mint_into(dest: &AccountId, amount: Self::Balance) -> Result<Self::Balance, DispatchError> {
+		Currency::mint_into(dest, amount)
+	}
+
+	fn burn_from(
+		dest: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		fortitude: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::burn_from(dest, amount, precision, fortitude)
+	}
+
+	fn transfer(
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		preservation: Preservation,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::transfer(source, dest, amount, preservation)
+	}
+}
+
+impl<T, AccountId, Currency, Amount, Moment> fungible::InspectHold<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::InspectHold<AccountId>,
+{
+	type Reason = <Currency as fungible::InspectHold<AccountId>>::Reason;
+
+	fn total_balance_on_hold(who: &AccountId) -> Self::Balance {
+		Currency::total_balance_on_hold(who)
+	}
+
+	fn reducible_total_balance_on_hold(who: &AccountId, force: Fortitude) -> Self::Balance {
+		Currency::reducible_total_balance_on_hold(who, force)
+	}
+
+	fn balance_on_hold(reason: &Self::Reason, who: &AccountId) -> Self::Balance {
+		Currency::balance_on_hold(reason, who)
+	}
+
+	fn hold_available(reason: &Self::Reason, who: &AccountId) -> bool {
+		Currency::hold_available(reason, who)
+	}
+
+	fn can_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> bool {
+		Currency::can_hold(reason, who, amount)
+	}
+}
+
+impl<T, AccountId, Currency, Amount, Moment> fungible::UnbalancedHold<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::UnbalancedHold<AccountId>,
+{
+	fn set_balance_on_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		Currency::set_balance_on_hold(reason, who, amount)
+	}
+}
+
+impl<T, AccountId, Currency, Amount, Moment> fungible::MutateHold<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::MutateHold<AccountId>,
+{
+	fn hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		Currency::hold(reason, who, amount)
+	}
+
+	fn release(
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::release(reason, who, amount, precision)
+	}
+
+	fn transfer_on_hold(
+		reason: &Self::Reason,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		restriction: Restriction,
+		fortitude: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::transfer_on_hold(reason, source, dest, amount, precision, restriction, fortitude)
+	}
+}
@@ -9,6 +9,11 @@
 //! It also provides an adapter, to adapt `frame_support::traits::Currency`
 //! implementations into `BasicCurrencyExtended`.
 //!
+//! The non-native currencies can be split across several multi-currencies,
+//! e.g. `orml_tokens` and an assets pallet, by using `RoutedMultiCurrency` as
+//! `Config::MultiCurrency`, with a `CurrencyRouter` choosing the backend of
+//! each currency.
+//!
 //! The currencies module provides functionality of both `MultiCurrencyExtended`
 //! and `BasicCurrencyExtended`, via unified interfaces, and all calls would be
 //! delegated to the underlying multi-currency and base currency system.
@@ -23,6 +28,11 @@
 //! - `MultiCurrencyExtended` - Extended `MultiCurrency` with additional helper
 //!   types and methods, like updating balance
 //! by a given signed integer amount.
+//! - `fungibles::{Inspect, Mutate, InspectHold, MutateHold}` - The
+//!   `frame_support` fungibles family. The hold traits are only implemented
+//!   if both the native currency and the multi-currency implement their
+//!   `fungible` and `fungibles` counterparts. `BasicCurrencyAdapter`
+//!   implements them for any `fungible` currency.
 //!
 //! ## Interface
 //!
@@ -35,6 +45,12 @@
 //! `Config::NativeCurrency`.
 //! - `update_balance` - Update balance by signed integer amount, in a given
 //!   currency, root origin required.
+//! - `transfer_with_memo` - Transfer some balance to another account with a
+//!   memo attached, in a given currency.
+//! - `transfer_all` - Transfer all transferable balance to another account, in
+//!   a given currency.
+//! - `transfer_keep_alive` - Transfer some balance to another account without
+//!   killing the sender, in a given currency.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -42,6 +58,8 @@
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
+		fungible, fungibles,
+		tokens::{Fortitude, Preservation},
 		Currency as PalletCurrency, ExistenceRequirement, Get, Imbalance, LockableCurrency as PalletLockableCurrency,
 		NamedReservableCurrency as PalletNamedReservableCurrency, ReservableCurrency as PalletReservableCurrency,
 		WithdrawReasons,
@@ -63,11 +81,16 @@ use sp_runtime::{
 };
 use sp_std::{fmt::Debug, marker, result};
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod impls;
 mod mock;
+mod router;
 mod tests;
 mod weights;
 
 pub use module::*;
+pub use router::{CurrencyRouter, RoutedMultiCurrency};
 pub use weights::WeightInfo;
 
 #[frame_support::pallet]
@@ -86,22 +109,35 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
 		type MultiCurrency: TransferAll<Self::AccountId>
 			+ MultiCurrencyExtended<Self::AccountId>
 			+ MultiLockableCurrency<Self::AccountId>
 			+ MultiReservableCurrency<Self::AccountId>
-			+ NamedMultiReservableCurrency<Self::AccountId>;
+			+ NamedMultiReservableCurrency<Self::AccountId>
+			+ fungibles::Mutate<Self::AccountId, AssetId = CurrencyIdOf<Self>, Balance = BalanceOf<Self>>;
 
 		type NativeCurrency: BasicCurrencyExtended<Self::AccountId, Balance = BalanceOf<Self>, Amount = AmountOf<Self>>
 			+ BasicLockableCurrency<Self::AccountId, Balance = BalanceOf<Self>>
 			+ BasicReservableCurrency<Self::AccountId, Balance = BalanceOf<Self>>
-			+ NamedBasicReservableCurrency<Self::AccountId, ReserveIdentifierOf<Self>, Balance = BalanceOf<Self>>;
+			+ NamedBasicReservableCurrency<Self::AccountId, ReserveIdentifierOf<Self>, Balance = BalanceOf<Self>>
+			+ fungible::Mutate<Self::AccountId, Balance = BalanceOf<Self>>;
 
 		#[pallet::constant]
 		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
 
+		/// The maximum length of the memo attached to a transfer by
+		/// `transfer_with_memo`.
+		#[pallet::constant]
+		type MaxMemoLength: Get<u32>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
+
+		/// A non-native currency used by the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type GetBenchmarkCurrencyId: Get<CurrencyIdOf<Self>>;
 	}
 
 	#[pallet::error]
@@ -114,6 +150,20 @@ pub mod module {
 		DepositFailed,
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A memo was attached to a transfer, deposited right after the
+		/// transfer event of the underlying currency.
+		TransferMemo {
+			currency_id: CurrencyIdOf<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+			memo: BoundedVec<u8, T::MaxMemoLength>,
+		},
+	}
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -152,7 +202,7 @@ pub mod module {
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			T::NativeCurrency::transfer(&from, &to, amount)
+			<T::NativeCurrency as BasicCurrency<_>>::transfer(&from, &to, amount)
 		}
 
 		/// update amount of account `who` under `currency_id`.
@@ -170,6 +220,96 @@ pub mod module {
 			let dest = T::Lookup::lookup(who)?;
 			<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)
 		}
+
+		/// Transfer some balance to another account under `currency_id`,
+		/// which may be the native currency, attaching a memo to the transfer.
+		///
+		/// The memo is emitted in a `TransferMemo` event and isn't stored.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::transfer_with_memo())]
+		pub fn transfer_with_memo(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			memo: BoundedVec<u8, T::MaxMemoLength>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
+
+			Self::deposit_event(Event::TransferMemo {
+				currency_id,
+				from,
+				to,
+				amount,
+				memo,
+			});
+			Ok(())
+		}
+
+		/// Transfer all remaining balance under `currency_id`, which may be
+		/// the native currency, to the given account.
+		///
+		/// NOTE: This function only attempts to transfer _transferable_
+		/// balances. This means that any locked, reserved, or existential
+		/// deposits (when `keep_alive` is `true`), will not be transferred by
+		/// this function.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			T::WeightInfo::transfer_all_native_currency()
+				.max(T::WeightInfo::transfer_all_non_native_currency())
+		)]
+		pub fn transfer_all(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			keep_alive: bool,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let preservation = if keep_alive {
+				Preservation::Preserve
+			} else {
+				Preservation::Expendable
+			};
+			let reducible_balance = <Self as fungibles::Inspect<T::AccountId>>::reducible_balance(
+				currency_id,
+				&from,
+				preservation,
+				Fortitude::Polite,
+			);
+			<Self as fungibles::Mutate<_>>::transfer(currency_id, &from, &to, reducible_balance, preservation)
+				.map(|_| ())
+		}
+
+		/// Same as the [`transfer`] call, but with a check that the transfer
+		/// will not kill the origin account, for the native currency too.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			T::WeightInfo::transfer_keep_alive_native_currency()
+				.max(T::WeightInfo::transfer_keep_alive_non_native_currency())
+		)]
+		pub fn transfer_keep_alive(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as fungibles::Mutate<_>>::transfer(currency_id, &from, &to, amount, Preservation::Preserve)
+				.map(|_| ())
+		}
 	}
 }
 
@@ -179,25 +319,25 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 
 	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::minimum_balance()
+			<T::NativeCurrency as BasicCurrency<_>>::minimum_balance()
 		} else {
-			T::MultiCurrency::minimum_balance(currency_id)
+			<T::MultiCurrency as MultiCurrency<_>>::minimum_balance(currency_id)
 		}
 	}
 
 	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::total_issuance()
+			<T::NativeCurrency as BasicCurrency<_>>::total_issuance()
 		} else {
-			T::MultiCurrency::total_issuance(currency_id)
+			<T::MultiCurrency as MultiCurrency<_>>::total_issuance(currency_id)
 		}
 	}
 
 	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::total_balance(who)
+			<T::NativeCurrency as BasicCurrency<_>>::total_balance(who)
 		} else {
-			T::MultiCurrency::total_balance(currency_id, who)
+			<T::MultiCurrency as MultiCurrency<_>>::total_balance(currency_id, who)
 		}
 	}
 
@@ -227,9 +367,9 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 			return Ok(());
 		}
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::transfer(from, to, amount)
+			<T::NativeCurrency as BasicCurrency<_>>::transfer(from, to, amount)
 		} else {
-			T::MultiCurrency::transfer(currency_id, from, to, amount)
+			<T::MultiCurrency as MultiCurrency<_>>::transfer(currency_id, from, to, amount)
 		}
 	}
 
@@ -313,6 +453,34 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		}
 	}
 
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			T::NativeCurrency::set_lock_with_reasons(lock_id, who, amount, reasons)
+		} else {
+			T::MultiCurrency::set_lock_with_reasons(lock_id, currency_id, who, amount, reasons)
+		}
+	}
+
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			T::NativeCurrency::extend_lock_with_reasons(lock_id, who, amount, reasons)
+		} else {
+			T::MultiCurrency::extend_lock_with_reasons(lock_id, currency_id, who, amount, reasons)
+		}
+	}
+
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
 		if currency_id == T::GetNativeCurrencyId::get() {
 			T::NativeCurrency::remove_lock(lock_id, who)
@@ -525,6 +693,36 @@ where
 		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::extend_lock(lock_id, GetCurrencyId::get(), who, amount)
 	}
 
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::set_lock_with_reasons(
+			lock_id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		)
+	}
+
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::extend_lock_with_reasons(
+			lock_id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		)
+	}
+
 	fn remove_lock(lock_id: LockIdentifier, who: &T::AccountId) -> DispatchResult {
 		<Pallet<T> as MultiLockableCurrency<T::AccountId>>::remove_lock(lock_id, GetCurrencyId::get(), who)
 	}
@@ -733,6 +931,26 @@ where
 		Ok(())
 	}
 
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Currency::set_lock(lock_id, who, amount, reasons);
+		Ok(())
+	}
+
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Currency::extend_lock(lock_id, who, amount, reasons);
+		Ok(())
+	}
+
 	fn remove_lock(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult {
 		Currency::remove_lock(lock_id, who);
 		Ok(())
@@ -819,7 +1037,7 @@ impl<T: Config> TransferAll<T::AccountId> for Pallet<T> {
 			T::MultiCurrency::transfer_all(source, dest)?;
 
 			// transfer all free to dest
-			T::NativeCurrency::transfer(source, dest, T::NativeCurrency::free_balance(source))
+			<T::NativeCurrency as BasicCurrency<_>>::transfer(source, dest, T::NativeCurrency::free_balance(source))
 		})
 	}
 }
@@ -5,7 +5,7 @@
 use super::*;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{ConstU32, ConstU64, Everything, Nothing},
+	traits::{ConstU32, ConstU64, Everything, Nothing, VariantCount},
 	PalletId,
 };
 use orml_traits::{currency::MutationHooks, parameter_type_with_key};
@@ -19,6 +19,16 @@ use crate as currencies;
 
 pub type ReserveIdentifier = [u8; 8];
 
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub enum TestId {
+	Foo,
+	Bar,
+}
+
+impl VariantCount for TestId {
+	const VARIANT_COUNT: u32 = 2;
+}
+
 pub type AccountId = AccountId32;
 impl frame_system::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
@@ -46,7 +56,7 @@ impl frame_system::Config for Runtime {
 	type MaxConsumers = ConstU32<16>;
 }
 
-type CurrencyId = u32;
+pub type CurrencyId = u32;
 type Balance = u64;
 
 impl pallet_balances::Config for Runtime {
@@ -59,10 +69,10 @@ impl pallet_balances::Config for Runtime {
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = ReserveIdentifier;
 	type WeightInfo = ();
-	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeHoldReason = TestId;
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type FreezeIdentifier = [u8; 8];
-	type MaxHolds = ();
+	type MaxHolds = ConstU32<2>;
 	type MaxFreezes = ();
 }
 
@@ -72,6 +82,12 @@ parameter_type_with_key! {
 	};
 }
 
+parameter_type_with_key! {
+	pub MaxTotalIssuance: |_currency_id: CurrencyId| -> Option<Balance> {
+		None
+	};
+}
+
 parameter_types! {
 	pub DustAccount: AccountId = PalletId(*b"orml/dst").into_account_truncating();
 }
@@ -103,6 +119,24 @@ impl orml_tokens::Config for Runtime {
 	type MaxReserves = ConstU32<100_000>;
 	type ReserveIdentifier = ReserveIdentifier;
 	type DustRemovalWhitelist = Nothing;
+	type RuntimeHoldReason = TestId;
+	type MaxHolds = ConstU32<1>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ConstU32<1>;
+	type MaxTotalIssuance = MaxTotalIssuance;
+	type ApprovalDeposits = ExistentialDeposits;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxBatchTransfers = ConstU32<10>;
+	type MultiBlockMigrations = ();
+	type MaxReapAccounts = ConstU32<10>;
+	type CheckpointCurrencies = frame_support::traits::Nothing;
+	type MaxCheckpoints = ConstU32<0>;
+	type IsSufficient = frame_support::traits::Everything;
+	type ComplianceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransferAllCurrencies = ConstU32<10>;
+	type MaxMemoLength = ConstU32<32>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type GetBenchmarkCurrencyId = ConstU32<X_TOKEN_ID>;
 }
 
 pub const NATIVE_CURRENCY_ID: CurrencyId = 1;
@@ -113,14 +147,32 @@ parameter_types! {
 }
 
 impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type MultiCurrency = Tokens;
 	type NativeCurrency = AdaptedBasicCurrency;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type MaxMemoLength = ConstU32<32>;
 	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type GetBenchmarkCurrencyId = ConstU32<X_TOKEN_ID>;
 }
 pub type NativeCurrency = NativeCurrencyOf<Runtime>;
 pub type AdaptedBasicCurrency = BasicCurrencyAdapter<Runtime, PalletBalances, i64, u64>;
 
+pub struct MockRouter;
+impl CurrencyRouter<CurrencyId> for MockRouter {
+	fn route(currency_id: &CurrencyId) -> Option<u8> {
+		match *currency_id {
+			X_TOKEN_ID => Some(0),
+			NATIVE_CURRENCY_ID => Some(1),
+			_ => None,
+		}
+	}
+}
+
+/// Routes `X_TOKEN_ID` to `Tokens` and the native currency to `Currencies`.
+pub type RoutedCurrency = RoutedMultiCurrency<MockRouter, (Tokens, Currencies)>;
+
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
 construct_runtime!(
@@ -182,6 +234,7 @@ impl ExtBuilder {
 				.into_iter()
 				.filter(|(_, currency_id, _)| *currency_id != NATIVE_CURRENCY_ID)
 				.collect::<Vec<_>>(),
+			..Default::default()
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -0,0 +1,642 @@
+use frame_support::traits::{
+	fungibles,
+	tokens::{DepositConsequence, Fortitude, Precision, Preservation, Provenance, WithdrawConsequence},
+	WithdrawReasons,
+};
+use orml_traits::{
+	currency::TransferAll, BalanceStatus, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
+	MultiReservableCurrency, NamedMultiReservableCurrency,
+};
+use orml_utilities::with_transaction_result;
+use sp_runtime::{traits::Zero, DispatchError, DispatchResult, TokenError};
+use sp_std::{marker, result};
+
+/// Maps a currency to the backend of `RoutedMultiCurrency` handling it.
+pub trait CurrencyRouter<CurrencyId> {
+	/// The index of the backend handling `currency_id` in the tuple of
+	/// backends, `None` if no backend handles it.
+	fn route(currency_id: &CurrencyId) -> Option<u8>;
+}
+
+/// A multi-currency dispatching each operation to one of `Backends`, a tuple
+/// of up to four multi-currencies, as chosen by `Router`.
+///
+/// It can be used as `Config::MultiCurrency` to route the non-native
+/// currencies to more than one balance source. Operations on currencies
+/// which aren't routed fail with `TokenError::UnknownAsset`, and their
+/// balances are zero. `TransferAll` isn't routed, but goes to every backend.
+pub struct RoutedMultiCurrency<Router, Backends>(marker::PhantomData<(Router, Backends)>);
+
+// Dispatch `$method $args` of `$tr` to the backend `Router` picks for
+// `$currency_id`, or evaluate `$default` if there is none.
+macro_rules! route {
+	($currency_id:expr, $tr:path, $method:ident $args:tt, $default:expr; $($backend:ident $index:literal),+) => {
+		match Router::route(&$currency_id) {
+			$(Some($index) => <$backend as $tr>::$method $args,)+
+			_ => $default,
+		}
+	};
+}
+
+macro_rules! impl_routed_multi_currency {
+	($first:ident $first_index:literal $(, $backend:ident $index:literal)*) => {
+		impl<AccountId, Router, $first, $($backend),*> MultiCurrency<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			Router: CurrencyRouter<<$first as MultiCurrency<AccountId>>::CurrencyId>,
+			$first: MultiCurrency<AccountId>,
+			$($backend: MultiCurrency<
+				AccountId,
+				CurrencyId = <$first as MultiCurrency<AccountId>>::CurrencyId,
+				Balance = <$first as MultiCurrency<AccountId>>::Balance,
+			>,)*
+		{
+			type CurrencyId = <$first as MultiCurrency<AccountId>>::CurrencyId;
+			type Balance = <$first as MultiCurrency<AccountId>>::Balance;
+
+			fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
+				route!(
+					currency_id, MultiCurrency<AccountId>, minimum_balance(currency_id), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
+				route!(
+					currency_id, MultiCurrency<AccountId>, total_issuance(currency_id), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn total_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance {
+				route!(
+					currency_id, MultiCurrency<AccountId>, total_balance(currency_id, who), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn free_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance {
+				route!(
+					currency_id, MultiCurrency<AccountId>, free_balance(currency_id, who), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn ensure_can_withdraw(
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				amount: Self::Balance,
+			) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiCurrency<AccountId>,
+					ensure_can_withdraw(currency_id, who, amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn transfer(
+				currency_id: Self::CurrencyId,
+				from: &AccountId,
+				to: &AccountId,
+				amount: Self::Balance,
+			) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiCurrency<AccountId>,
+					transfer(currency_id, from, to, amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn deposit(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiCurrency<AccountId>,
+					deposit(currency_id, who, amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn withdraw(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiCurrency<AccountId>,
+					withdraw(currency_id, who, amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn can_slash(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> bool {
+				route!(
+					currency_id, MultiCurrency<AccountId>, can_slash(currency_id, who, value), value.is_zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn slash(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Self::Balance {
+				route!(
+					currency_id, MultiCurrency<AccountId>, slash(currency_id, who, amount), amount;
+					$first $first_index $(, $backend $index)*
+				)
+			}
+		}
+
+		impl<AccountId, Router, $first, $($backend),*> MultiCurrencyExtended<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			Router: CurrencyRouter<<$first as MultiCurrency<AccountId>>::CurrencyId>,
+			$first: MultiCurrencyExtended<AccountId>,
+			$($backend: MultiCurrencyExtended<
+				AccountId,
+				CurrencyId = <$first as MultiCurrency<AccountId>>::CurrencyId,
+				Balance = <$first as MultiCurrency<AccountId>>::Balance,
+				Amount = <$first as MultiCurrencyExtended<AccountId>>::Amount,
+			>,)*
+		{
+			type Amount = <$first as MultiCurrencyExtended<AccountId>>::Amount;
+
+			fn update_balance(
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				by_amount: Self::Amount,
+			) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiCurrencyExtended<AccountId>,
+					update_balance(currency_id, who, by_amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+		}
+
+		impl<AccountId, Router, $first, $($backend),*> MultiLockableCurrency<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			Router: CurrencyRouter<<$first as MultiCurrency<AccountId>>::CurrencyId>,
+			$first: MultiLockableCurrency<AccountId>,
+			$($backend: MultiLockableCurrency<
+				AccountId,
+				CurrencyId = <$first as MultiCurrency<AccountId>>::CurrencyId,
+				Balance = <$first as MultiCurrency<AccountId>>::Balance,
+				Moment = <$first as MultiLockableCurrency<AccountId>>::Moment,
+			>,)*
+		{
+			type Moment = <$first as MultiLockableCurrency<AccountId>>::Moment;
+
+			fn set_lock(
+				lock_id: LockIdentifier,
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				amount: Self::Balance,
+			) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiLockableCurrency<AccountId>,
+					set_lock(lock_id, currency_id, who, amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn extend_lock(
+				lock_id: LockIdentifier,
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				amount: Self::Balance,
+			) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiLockableCurrency<AccountId>,
+					extend_lock(lock_id, currency_id, who, amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn set_lock_with_reasons(
+				lock_id: LockIdentifier,
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				amount: Self::Balance,
+				reasons: WithdrawReasons,
+			) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiLockableCurrency<AccountId>,
+					set_lock_with_reasons(lock_id, currency_id, who, amount, reasons),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn extend_lock_with_reasons(
+				lock_id: LockIdentifier,
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				amount: Self::Balance,
+				reasons: WithdrawReasons,
+			) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiLockableCurrency<AccountId>,
+					extend_lock_with_reasons(lock_id, currency_id, who, amount, reasons),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &AccountId) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiLockableCurrency<AccountId>,
+					remove_lock(lock_id, currency_id, who),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+		}
+
+		impl<AccountId, Router, $first, $($backend),*> MultiReservableCurrency<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			Router: CurrencyRouter<<$first as MultiCurrency<AccountId>>::CurrencyId>,
+			$first: MultiReservableCurrency<AccountId>,
+			$($backend: MultiReservableCurrency<
+				AccountId,
+				CurrencyId = <$first as MultiCurrency<AccountId>>::CurrencyId,
+				Balance = <$first as MultiCurrency<AccountId>>::Balance,
+			>,)*
+		{
+			fn can_reserve(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> bool {
+				route!(
+					currency_id, MultiReservableCurrency<AccountId>, can_reserve(currency_id, who, value), value.is_zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn slash_reserved(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> Self::Balance {
+				route!(
+					currency_id, MultiReservableCurrency<AccountId>, slash_reserved(currency_id, who, value), value;
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn reserved_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance {
+				route!(
+					currency_id, MultiReservableCurrency<AccountId>, reserved_balance(currency_id, who), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn reserve(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> DispatchResult {
+				route!(
+					currency_id,
+					MultiReservableCurrency<AccountId>,
+					reserve(currency_id, who, value),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn unreserve(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> Self::Balance {
+				route!(
+					currency_id, MultiReservableCurrency<AccountId>, unreserve(currency_id, who, value), value;
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn repatriate_reserved(
+				currency_id: Self::CurrencyId,
+				slashed: &AccountId,
+				beneficiary: &AccountId,
+				value: Self::Balance,
+				status: BalanceStatus,
+			) -> result::Result<Self::Balance, DispatchError> {
+				route!(
+					currency_id,
+					MultiReservableCurrency<AccountId>,
+					repatriate_reserved(currency_id, slashed, beneficiary, value, status),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+		}
+
+		impl<AccountId, Router, $first, $($backend),*> NamedMultiReservableCurrency<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			Router: CurrencyRouter<<$first as MultiCurrency<AccountId>>::CurrencyId>,
+			$first: NamedMultiReservableCurrency<AccountId>,
+			$($backend: NamedMultiReservableCurrency<
+				AccountId,
+				CurrencyId = <$first as MultiCurrency<AccountId>>::CurrencyId,
+				Balance = <$first as MultiCurrency<AccountId>>::Balance,
+				ReserveIdentifier = <$first as NamedMultiReservableCurrency<AccountId>>::ReserveIdentifier,
+			>,)*
+		{
+			type ReserveIdentifier = <$first as NamedMultiReservableCurrency<AccountId>>::ReserveIdentifier;
+
+			fn slash_reserved_named(
+				id: &Self::ReserveIdentifier,
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				value: Self::Balance,
+			) -> Self::Balance {
+				route!(
+					currency_id,
+					NamedMultiReservableCurrency<AccountId>,
+					slash_reserved_named(id, currency_id, who, value),
+					value;
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn reserved_balance_named(
+				id: &Self::ReserveIdentifier,
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+			) -> Self::Balance {
+				route!(
+					currency_id,
+					NamedMultiReservableCurrency<AccountId>,
+					reserved_balance_named(id, currency_id, who),
+					Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn reserve_named(
+				id: &Self::ReserveIdentifier,
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				value: Self::Balance,
+			) -> DispatchResult {
+				route!(
+					currency_id,
+					NamedMultiReservableCurrency<AccountId>,
+					reserve_named(id, currency_id, who, value),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn unreserve_named(
+				id: &Self::ReserveIdentifier,
+				currency_id: Self::CurrencyId,
+				who: &AccountId,
+				value: Self::Balance,
+			) -> Self::Balance {
+				route!(
+					currency_id,
+					NamedMultiReservableCurrency<AccountId>,
+					unreserve_named(id, currency_id, who, value),
+					value;
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn repatriate_reserved_named(
+				id: &Self::ReserveIdentifier,
+				currency_id: Self::CurrencyId,
+				slashed: &AccountId,
+				beneficiary: &AccountId,
+				value: Self::Balance,
+				status: BalanceStatus,
+			) -> result::Result<Self::Balance, DispatchError> {
+				route!(
+					currency_id,
+					NamedMultiReservableCurrency<AccountId>,
+					repatriate_reserved_named(id, currency_id, slashed, beneficiary, value, status),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+		}
+
+		/// `TransferAll` has no currency to route, so every backend transfers all
+		/// the balances it holds, in the order of the tuple, and all transfers
+		/// are reverted if one fails. This includes the currencies a backend
+		/// holds which aren't routed to it, even though their balances are zero
+		/// for `RoutedMultiCurrency`.
+		impl<AccountId, Router, $first, $($backend),*> TransferAll<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			$first: TransferAll<AccountId>,
+			$($backend: TransferAll<AccountId>,)*
+		{
+			fn transfer_all(source: &AccountId, dest: &AccountId) -> DispatchResult {
+				with_transaction_result(|| {
+					<$first as TransferAll<AccountId>>::transfer_all(source, dest)?;
+					$(<$backend as TransferAll<AccountId>>::transfer_all(source, dest)?;)*
+					Ok(())
+				})
+			}
+		}
+
+		impl<AccountId, Router, $first, $($backend),*> fungibles::Inspect<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			Router: CurrencyRouter<<$first as fungibles::Inspect<AccountId>>::AssetId>,
+			$first: fungibles::Inspect<AccountId>,
+			$($backend: fungibles::Inspect<
+				AccountId,
+				AssetId = <$first as fungibles::Inspect<AccountId>>::AssetId,
+				Balance = <$first as fungibles::Inspect<AccountId>>::Balance,
+			>,)*
+		{
+			type AssetId = <$first as fungibles::Inspect<AccountId>>::AssetId;
+			type Balance = <$first as fungibles::Inspect<AccountId>>::Balance;
+
+			fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+				route!(
+					asset, fungibles::Inspect<AccountId>, total_issuance(asset), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn minimum_balance(asset: Self::AssetId) -> Self::Balance {
+				route!(
+					asset, fungibles::Inspect<AccountId>, minimum_balance(asset), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+				route!(
+					asset, fungibles::Inspect<AccountId>, balance(asset, who), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn total_balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+				route!(
+					asset, fungibles::Inspect<AccountId>, total_balance(asset, who), Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn reducible_balance(
+				asset: Self::AssetId,
+				who: &AccountId,
+				preservation: Preservation,
+				fortitude: Fortitude,
+			) -> Self::Balance {
+				route!(
+					asset,
+					fungibles::Inspect<AccountId>,
+					reducible_balance(asset, who, preservation, fortitude),
+					Zero::zero();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn can_deposit(
+				asset: Self::AssetId,
+				who: &AccountId,
+				amount: Self::Balance,
+				provenance: Provenance,
+			) -> DepositConsequence {
+				route!(
+					asset,
+					fungibles::Inspect<AccountId>,
+					can_deposit(asset, who, amount, provenance),
+					DepositConsequence::UnknownAsset;
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn can_withdraw(
+				asset: Self::AssetId,
+				who: &AccountId,
+				amount: Self::Balance,
+			) -> WithdrawConsequence<Self::Balance> {
+				route!(
+					asset,
+					fungibles::Inspect<AccountId>,
+					can_withdraw(asset, who, amount),
+					WithdrawConsequence::UnknownAsset;
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn asset_exists(asset: Self::AssetId) -> bool {
+				route!(
+					asset, fungibles::Inspect<AccountId>, asset_exists(asset), false;
+					$first $first_index $(, $backend $index)*
+				)
+			}
+		}
+
+		impl<AccountId, Router, $first, $($backend),*> fungibles::Unbalanced<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			Router: CurrencyRouter<<$first as fungibles::Inspect<AccountId>>::AssetId>,
+			$first: fungibles::Unbalanced<AccountId>,
+			$($backend: fungibles::Unbalanced<
+				AccountId,
+				AssetId = <$first as fungibles::Inspect<AccountId>>::AssetId,
+				Balance = <$first as fungibles::Inspect<AccountId>>::Balance,
+			>,)*
+		{
+			fn handle_dust(dust: fungibles::Dust<AccountId, Self>) {
+				let fungibles::Dust(asset, amount) = dust;
+				route!(
+					asset,
+					fungibles::Unbalanced<AccountId>,
+					handle_dust(fungibles::Dust(asset, amount)),
+					();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn write_balance(
+				asset: Self::AssetId,
+				who: &AccountId,
+				amount: Self::Balance,
+			) -> Result<Option<Self::Balance>, DispatchError> {
+				route!(
+					asset,
+					fungibles::Unbalanced<AccountId>,
+					write_balance(asset, who, amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn set_total_issuance(asset: Self::AssetId, amount: Self::Balance) {
+				route!(
+					asset, fungibles::Unbalanced<AccountId>, set_total_issuance(asset, amount), ();
+					$first $first_index $(, $backend $index)*
+				)
+			}
+		}
+
+		impl<AccountId, Router, $first, $($backend),*> fungibles::Mutate<AccountId>
+			for RoutedMultiCurrency<Router, ($first, $($backend),*)>
+		where
+			Router: CurrencyRouter<<$first as fungibles::Inspect<AccountId>>::AssetId>,
+			$first: fungibles::Mutate<AccountId>,
+			$($backend: fungibles::Mutate<
+				AccountId,
+				AssetId = <$first as fungibles::Inspect<AccountId>>::AssetId,
+				Balance = <$first as fungibles::Inspect<AccountId>>::Balance,
+			>,)*
+		{
+			fn mint_into(
+				asset: Self::AssetId,
+				dest: &AccountId,
+				amount: Self::Balance,
+			) -> Result<Self::Balance, DispatchError> {
+				route!(
+					asset,
+					fungibles::Mutate<AccountId>,
+					mint_into(asset, dest, amount),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn burn_from(
+				asset: Self::AssetId,
+				dest: &AccountId,
+				amount: Self::Balance,
+				precision: Precision,
+				fortitude: Fortitude,
+			) -> Result<Self::Balance, DispatchError> {
+				route!(
+					asset,
+					fungibles::Mutate<AccountId>,
+					burn_from(asset, dest, amount, precision, fortitude),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+
+			fn transfer(
+				asset: Self::AssetId,
+				source: &AccountId,
+				dest: &AccountId,
+				amount: Self::Balance,
+				preservation: Preservation,
+			) -> Result<Self::Balance, DispatchError> {
+				route!(
+					asset,
+					fungibles::Mutate<AccountId>,
+					transfer(asset, source, dest, amount, preservation),
+					Err(TokenError::UnknownAsset.into());
+					$first $first_index $(, $backend $index)*
+				)
+			}
+		}
+	};
+}
+
+impl_routed_multi_currency!(A 0, B 1);
+impl_routed_multi_currency!(A 0, B 1, C 2);
+impl_routed_multi_currency!(A 0, B 1, C 2, D 3);
@@ -3,9 +3,15 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungibles,
+		tokens::{Fortitude, Precision, Preservation, Restriction},
+	},
+};
 use mock::*;
-use sp_runtime::traits::BadOrigin;
+use sp_runtime::{traits::BadOrigin, TokenError};
 
 #[test]
 fn multi_lockable_currency_should_work() {
@@ -20,6 +26,43 @@ fn multi_lockable_currency_should_work() {
 		});
 }
 
+#[test]
+fn multi_lockable_currency_with_reasons_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::set_lock_with_reasons(
+				ID_1,
+				X_TOKEN_ID,
+				&ALICE,
+				50,
+				WithdrawReasons::RESERVE
+			));
+			assert_eq!(
+				Tokens::locks(&ALICE, X_TOKEN_ID)[0].reasons,
+				orml_tokens::LockReasons::from(WithdrawReasons::RESERVE)
+			);
+
+			assert_ok!(Currencies::set_lock_with_reasons(
+				ID_1,
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				50,
+				WithdrawReasons::TRANSFER
+			));
+			assert_eq!(PalletBalances::locks(&ALICE)[0].reasons, pallet_balances::Reasons::Misc);
+			assert_ok!(Currencies::extend_lock_with_reasons(
+				ID_1,
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				50,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			));
+			assert_eq!(PalletBalances::locks(&ALICE)[0].reasons, pallet_balances::Reasons::All);
+		});
+}
+
 #[test]
 fn multi_reservable_currency_should_work() {
 	ExtBuilder::default()
@@ -407,3 +450,362 @@ fn call_event_should_work() {
 			}));
 		});
 }
+
+#[test]
+fn transfer_with_memo_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let memo: BoundedVec<u8, <Runtime as Config>::MaxMemoLength> = b"deposit-7".to_vec().try_into().unwrap();
+
+			assert_ok!(Currencies::transfer_with_memo(
+				Some(ALICE).into(),
+				BOB,
+				X_TOKEN_ID,
+				50,
+				memo.clone()
+			));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 50);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 150);
+			System::assert_has_event(RuntimeEvent::Tokens(orml_tokens::Event::Transfer {
+				currency_id: X_TOKEN_ID,
+				from: ALICE,
+				to: BOB,
+				amount: 50,
+			}));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::TransferMemo {
+				currency_id: X_TOKEN_ID,
+				from: ALICE,
+				to: BOB,
+				amount: 50,
+				memo: memo.clone(),
+			}));
+
+			assert_ok!(Currencies::transfer_with_memo(
+				Some(ALICE).into(),
+				BOB,
+				NATIVE_CURRENCY_ID,
+				10,
+				memo.clone()
+			));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 90);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 110);
+			System::assert_has_event(RuntimeEvent::PalletBalances(pallet_balances::Event::Transfer {
+				from: ALICE,
+				to: BOB,
+				amount: 10,
+			}));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::TransferMemo {
+				currency_id: NATIVE_CURRENCY_ID,
+				from: ALICE,
+				to: BOB,
+				amount: 10,
+				memo,
+			}));
+
+			assert_noop!(
+				Currencies::transfer_with_memo(Some(ALICE).into(), BOB, X_TOKEN_ID, 60, Default::default()),
+				orml_tokens::Error::<Runtime>::BalanceTooLow
+			);
+		});
+}
+
+#[test]
+fn fungibles_inspect_and_mutate_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::balance(NATIVE_CURRENCY_ID, &ALICE),
+				100
+			);
+			assert_eq!(<Currencies as fungibles::Inspect<_>>::balance(X_TOKEN_ID, &ALICE), 100);
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::total_issuance(NATIVE_CURRENCY_ID),
+				200
+			);
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::minimum_balance(NATIVE_CURRENCY_ID),
+				2
+			);
+			assert_eq!(<Currencies as fungibles::Inspect<_>>::minimum_balance(X_TOKEN_ID), 0);
+			assert!(<Currencies as fungibles::Inspect<_>>::asset_exists(NATIVE_CURRENCY_ID));
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::reducible_balance(
+					NATIVE_CURRENCY_ID,
+					&ALICE,
+					Preservation::Preserve,
+					Fortitude::Polite
+				),
+				98
+			);
+
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::mint_into(
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				10
+			));
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::mint_into(X_TOKEN_ID, &ALICE, 20));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 110);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &ALICE), 120);
+			assert_eq!(PalletBalances::total_issuance(), 210);
+
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::transfer(
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				&BOB,
+				30,
+				Preservation::Protect
+			));
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::transfer(
+				X_TOKEN_ID,
+				&ALICE,
+				&BOB,
+				40,
+				Preservation::Protect
+			));
+			assert_eq!(NativeCurrency::free_balance(&BOB), 130);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &BOB), 140);
+
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::burn_from(
+				NATIVE_CURRENCY_ID,
+				&BOB,
+				30,
+				Precision::Exact,
+				Fortitude::Polite
+			));
+			assert_eq!(NativeCurrency::free_balance(&BOB), 100);
+			assert_eq!(PalletBalances::total_issuance(), 180);
+		});
+}
+
+#[test]
+fn fungibles_hold_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			for currency_id in [NATIVE_CURRENCY_ID, X_TOKEN_ID] {
+				assert!(<Currencies as fungibles::InspectHold<_>>::can_hold(
+					currency_id,
+					&TestId::Foo,
+					&ALICE,
+					50
+				));
+				assert_ok!(<Currencies as fungibles::MutateHold<_>>::hold(
+					currency_id,
+					&TestId::Foo,
+					&ALICE,
+					50
+				));
+				assert_eq!(
+					<Currencies as fungibles::InspectHold<_>>::balance_on_hold(currency_id, &TestId::Foo, &ALICE),
+					50
+				);
+				assert_eq!(
+					<Currencies as fungibles::InspectHold<_>>::total_balance_on_hold(currency_id, &ALICE),
+					50
+				);
+				assert_eq!(<Currencies as fungibles::Inspect<_>>::balance(currency_id, &ALICE), 50);
+
+				assert_ok!(<Currencies as fungibles::MutateHold<_>>::transfer_on_hold(
+					currency_id,
+					&TestId::Foo,
+					&ALICE,
+					&BOB,
+					20,
+					Precision::Exact,
+					Restriction::Free,
+					Fortitude::Polite
+				));
+				assert_eq!(<Currencies as fungibles::Inspect<_>>::balance(currency_id, &BOB), 120);
+
+				assert_ok!(<Currencies as fungibles::MutateHold<_>>::release(
+					currency_id,
+					&TestId::Foo,
+					&ALICE,
+					30,
+					Precision::Exact
+				));
+				assert_eq!(
+					<Currencies as fungibles::InspectHold<_>>::balance_on_hold(currency_id, &TestId::Foo, &ALICE),
+					0
+				);
+				assert_eq!(<Currencies as fungibles::Inspect<_>>::balance(currency_id, &ALICE), 80);
+			}
+
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 80);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &ALICE), 80);
+		});
+}
+
+#[test]
+fn transfer_all_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::set_lock(ID_1, NATIVE_CURRENCY_ID, &ALICE, 10));
+			assert_ok!(Currencies::transfer_all(
+				Some(ALICE).into(),
+				BOB,
+				NATIVE_CURRENCY_ID,
+				true
+			));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 10);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 190);
+
+			assert_ok!(Currencies::transfer_all(Some(ALICE).into(), BOB, X_TOKEN_ID, false));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 200);
+
+			assert_ok!(Currencies::transfer_all(
+				Some(BOB).into(),
+				EVA,
+				NATIVE_CURRENCY_ID,
+				false
+			));
+			assert_eq!(NativeCurrency::free_balance(&BOB), 0);
+			assert_eq!(NativeCurrency::free_balance(&EVA), 190);
+		});
+}
+
+#[test]
+fn transfer_all_keep_alive_should_keep_source_account() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::transfer_all(
+				Some(ALICE).into(),
+				BOB,
+				NATIVE_CURRENCY_ID,
+				true
+			));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 2);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 198);
+			assert!(System::account_exists(&ALICE));
+		});
+}
+
+#[test]
+fn transfer_keep_alive_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::transfer_keep_alive(Some(ALICE).into(), BOB, NATIVE_CURRENCY_ID, 99),
+				TokenError::NotExpendable
+			);
+			assert_ok!(Currencies::transfer_keep_alive(
+				Some(ALICE).into(),
+				BOB,
+				NATIVE_CURRENCY_ID,
+				98
+			));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 2);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 198);
+
+			assert_ok!(Currencies::transfer_keep_alive(Some(ALICE).into(), BOB, X_TOKEN_ID, 50));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 50);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 150);
+		});
+}
+
+#[test]
+fn routed_multi_currency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			const UNKNOWN_TOKEN_ID: CurrencyId = 3;
+
+			assert_eq!(RoutedCurrency::free_balance(NATIVE_CURRENCY_ID, &ALICE), 100);
+			assert_eq!(RoutedCurrency::free_balance(X_TOKEN_ID, &ALICE), 100);
+			assert_eq!(RoutedCurrency::minimum_balance(NATIVE_CURRENCY_ID), 2);
+			assert_eq!(RoutedCurrency::free_balance(UNKNOWN_TOKEN_ID, &ALICE), 0);
+
+			assert_ok!(RoutedCurrency::transfer(NATIVE_CURRENCY_ID, &ALICE, &BOB, 10));
+			assert_eq!(PalletBalances::free_balance(&ALICE), 90);
+			assert_eq!(PalletBalances::free_balance(&BOB), 110);
+			assert_ok!(RoutedCurrency::transfer(X_TOKEN_ID, &ALICE, &BOB, 20));
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &ALICE), 80);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &BOB), 120);
+			assert_noop!(
+				RoutedCurrency::transfer(UNKNOWN_TOKEN_ID, &ALICE, &BOB, 20),
+				TokenError::UnknownAsset
+			);
+
+			assert_ok!(RoutedCurrency::update_balance(NATIVE_CURRENCY_ID, &ALICE, -10));
+			assert_eq!(PalletBalances::free_balance(&ALICE), 80);
+
+			assert_ok!(RoutedCurrency::set_lock(ID_1, NATIVE_CURRENCY_ID, &ALICE, 50));
+			assert_eq!(PalletBalances::locks(&ALICE).len(), 1);
+			assert_ok!(RoutedCurrency::set_lock(ID_1, X_TOKEN_ID, &ALICE, 50));
+			assert_eq!(Tokens::locks(&ALICE, X_TOKEN_ID).len(), 1);
+			assert_noop!(
+				RoutedCurrency::set_lock(ID_1, UNKNOWN_TOKEN_ID, &ALICE, 50),
+				TokenError::UnknownAsset
+			);
+
+			assert_ok!(RoutedCurrency::reserve(NATIVE_CURRENCY_ID, &ALICE, 10));
+			assert_eq!(PalletBalances::reserved_balance(&ALICE), 10);
+			assert_ok!(RoutedCurrency::reserve_named(&RID_1, X_TOKEN_ID, &ALICE, 30));
+			assert_eq!(Tokens::reserved_balance(X_TOKEN_ID, &ALICE), 30);
+			assert_eq!(RoutedCurrency::reserved_balance_named(&RID_1, X_TOKEN_ID, &ALICE), 30);
+			assert_eq!(RoutedCurrency::unreserve(UNKNOWN_TOKEN_ID, &ALICE, 10), 10);
+
+			assert_ok!(<RoutedCurrency as fungibles::Mutate<_>>::mint_into(
+				NATIVE_CURRENCY_ID,
+				&EVA,
+				10
+			));
+			assert_eq!(PalletBalances::free_balance(&EVA), 10);
+			assert_eq!(
+				<RoutedCurrency as fungibles::Inspect<_>>::balance(UNKNOWN_TOKEN_ID, &EVA),
+				0
+			);
+		});
+}
+
+#[test]
+fn routed_multi_currency_transfer_all_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			const UNKNOWN_TOKEN_ID: CurrencyId = 3;
+			assert_ok!(Tokens::deposit(UNKNOWN_TOKEN_ID, &ALICE, 50));
+
+			// every backend transfers all of its balances
+			assert_ok!(RoutedCurrency::transfer_all(&ALICE, &EVA));
+			assert_eq!(RoutedCurrency::free_balance(NATIVE_CURRENCY_ID, &EVA), 100);
+			assert_eq!(RoutedCurrency::free_balance(X_TOKEN_ID, &EVA), 100);
+			assert_eq!(RoutedCurrency::free_balance(NATIVE_CURRENCY_ID, &ALICE), 0);
+			assert_eq!(RoutedCurrency::free_balance(X_TOKEN_ID, &ALICE), 0);
+
+			// including the currencies which aren't routed
+			assert_eq!(RoutedCurrency::free_balance(UNKNOWN_TOKEN_ID, &EVA), 0);
+			assert_eq!(Tokens::free_balance(UNKNOWN_TOKEN_ID, &EVA), 50);
+			assert_eq!(Tokens::free_balance(UNKNOWN_TOKEN_ID, &ALICE), 0);
+		});
+}
+
+#[test]
+fn routed_multi_currency_transfer_all_should_revert_all_backends() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			// the native currency backend fails after the tokens backend transferred
+			assert_ok!(RoutedCurrency::set_lock(ID_1, NATIVE_CURRENCY_ID, &ALICE, 50));
+			assert!(RoutedCurrency::transfer_all(&ALICE, &EVA).is_err());
+			assert_eq!(RoutedCurrency::free_balance(X_TOKEN_ID, &ALICE), 100);
+			assert_eq!(RoutedCurrency::free_balance(X_TOKEN_ID, &EVA), 0);
+			assert_eq!(RoutedCurrency::free_balance(NATIVE_CURRENCY_ID, &ALICE), 100);
+		});
+}
@@ -35,6 +35,11 @@ pub trait WeightInfo {
 	fn update_balance_non_native_currency() -> Weight;
 	fn update_balance_native_currency_creating() -> Weight;
 	fn update_balance_native_currency_killing() -> Weight;
+	fn transfer_with_memo() -> Weight;
+	fn transfer_all_non_native_currency() -> Weight;
+	fn transfer_all_native_currency() -> Weight;
+	fn transfer_keep_alive_non_native_currency() -> Weight;
+	fn transfer_keep_alive_native_currency() -> Weight;
 }
 
 /// Default weights.
@@ -64,4 +69,32 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
 			.saturating_add(RocksDbWeight::get().writes(2 as u64))
 	}
+
+	// The weights below are hand-estimated, not produced by the benchmark CLI.
+	// Replace them with generated weights once these calls are benchmarked.
+	fn transfer_with_memo() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_all_non_native_currency() -> Weight {
+		Weight::from_parts(65_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_all_native_currency() -> Weight {
+		Weight::from_parts(70_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_keep_alive_non_native_currency() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_keep_alive_native_currency() -> Weight {
+		Weight::from_parts(65_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
 }
@@ -73,6 +73,12 @@ parameter_type_with_key! {
 		0u128
 	};
 }
+
+parameter_type_with_key! {
+	pub MaxTotalIssuance: |_currency_id: u32| -> Option<Balance> {
+		None
+	};
+}
 parameter_types! {
 	pub const MaxLocks: u32 = 50;
 }
@@ -97,6 +103,22 @@ impl orml_tokens::Config for Test {
 	type DustRemovalWhitelist = MockDustRemovalWhitelist;
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = ReserveIdentifier;
+	type RuntimeHoldReason = ();
+	type MaxHolds = ConstU32<1>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ConstU32<1>;
+	type MaxTotalIssuance = MaxTotalIssuance;
+	type ApprovalDeposits = ExistentialDeposits;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxBatchTransfers = ConstU32<10>;
+	type MultiBlockMigrations = ();
+	type MaxReapAccounts = ConstU32<10>;
+	type CheckpointCurrencies = frame_support::traits::Nothing;
+	type MaxCheckpoints = ConstU32<0>;
+	type IsSufficient = frame_support::traits::Everything;
+	type ComplianceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransferAllCurrencies = ConstU32<10>;
+	type MaxMemoLength = ConstU32<32>;
 }
 
 pub struct MockDisputeResolver;
@@ -149,6 +171,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			(PAYMENT_CREATOR, CURRENCY_ID, 100),
 			(PAYMENT_CREATOR_TWO, CURRENCY_ID, 100),
 		],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -6,7 +6,55 @@
 // The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
 #![allow(clippy::unnecessary_mut_passed)]
 
-use parity_scale_codec::Codec;
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
+
+/// Balances of an account in a single currency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AccountBalances<Balance> {
+	/// Balance that can be transferred, if not frozen.
+	pub free: Balance,
+	/// Balance that is reserved and can not be used.
+	pub reserved: Balance,
+	/// The amount of `free` that can not be transferred.
+	pub frozen: Balance,
+	/// The sum of `free` and `reserved`.
+	pub total: Balance,
+}
+
+/// A single lock on the balance of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BalanceLockInfo<Balance, BlockNumber> {
+	/// An identifier for this lock.
+	pub id: [u8; 8],
+	/// The amount which the free balance may not drop below.
+	pub amount: Balance,
+	/// The block at which this lock stops being in effect, `None` if the lock
+	/// lives until it is removed.
+	pub expires_at: Option<BlockNumber>,
+	/// The bits of the `WithdrawReasons` for which this lock prevents
+	/// withdrawals.
+	pub reasons: u8,
+}
+
+/// A single named reserve on the balance of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReserveInfo<ReserveIdentifier, Balance> {
+	/// The identifier of the reserve.
+	pub id: ReserveIdentifier,
+	/// The amount of the reserve.
+	pub amount: Balance,
+}
 
 sp_api::decl_runtime_apis! {
 	pub trait TokensApi<CurrencyId, Balance> where
@@ -15,4 +63,31 @@ sp_api::decl_runtime_apis! {
 	{
 		fn query_existential_deposit(currency_id: CurrencyId) -> Balance;
 	}
+
+	pub trait TokensAccountApi<AccountId, CurrencyId, Balance, ReserveIdentifier, BlockNumber> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+		ReserveIdentifier: Codec,
+		BlockNumber: Codec,
+	{
+		fn query_balances(currency_id: CurrencyId, who: AccountId) -> AccountBalances<Balance>;
+
+		fn query_locks(currency_id: CurrencyId, who: AccountId) -> Vec<BalanceLockInfo<Balance, BlockNumber>>;
+
+		fn query_reserves(currency_id: CurrencyId, who: AccountId) -> Vec<ReserveInfo<ReserveIdentifier, Balance>>;
+
+		fn query_account_currencies(who: AccountId) -> Vec<(CurrencyId, AccountBalances<Balance>)>;
+	}
+
+	pub trait TokensCheckpointApi<AccountId, CurrencyId, Balance, BlockNumber> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		fn query_balance_at(currency_id: CurrencyId, who: AccountId, block_number: BlockNumber) -> Option<Balance>;
+
+		fn query_total_issuance_at(currency_id: CurrencyId, block_number: BlockNumber) -> Option<Balance>;
+	}
 }
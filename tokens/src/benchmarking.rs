@@ -0,0 +1,51 @@
+//! Benchmarks for the tokens module.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+/// Fund `who` with a hundred times the existential deposit of `currency_id`
+/// for each of `transfers` transfers, or a thousand units each if there is
+/// none.
+fn fund<T: Config>(currency_id: T::CurrencyId, who: &T::AccountId, transfers: u32) -> T::Balance {
+	let amount = T::ExistentialDeposits::get(&currency_id)
+		.saturating_mul(100u32.into())
+		.max(1_000u32.into());
+	assert!(
+		<Pallet<T> as MultiCurrency<_>>::deposit(currency_id, who, amount.saturating_mul(transfers.into())).is_ok()
+	);
+	amount
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn transfer_batch(n: Linear<1, { T::MaxBatchTransfers::get() }>) {
+		let currency_id = T::GetBenchmarkCurrencyId::get();
+		let from: T::AccountId = whitelisted_caller();
+		let amount = fund::<T>(currency_id, &from, n);
+		let transfers: BatchTransfersOf<T> = (0..n)
+			.map(|i| (T::Lookup::unlookup(account("to", i, SEED)), currency_id, amount))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("n is at most MaxBatchTransfers");
+
+		#[extrinsic_call]
+		transfer_batch(RawOrigin::Signed(from.clone()), transfers);
+
+		assert_eq!(
+			<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &from),
+			Zero::zero()
+		);
+		assert_eq!(
+			<Pallet<T> as MultiCurrency<_>>::free_balance(currency_id, &account("to", n - 1, SEED)),
+			amount
+		);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
+}
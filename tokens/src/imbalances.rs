@@ -1,7 +1,8 @@
 // wrapping these imbalances in a private module is necessary to ensure absolute
 // privacy of the inner member.
-use crate::{Config, TotalIssuance};
+use crate::{Config, Pallet};
 use frame_support::traits::{Get, Imbalance, SameOrOther, TryDrop};
+use orml_traits::MultiImbalance;
 use sp_runtime::traits::{Saturating, Zero};
 use sp_std::{marker, mem, result};
 
@@ -174,13 +175,199 @@ impl<T: Config, GetCurrencyId: Get<T::CurrencyId>> Imbalance<T::Balance> for Neg
 impl<T: Config, GetCurrencyId: Get<T::CurrencyId>> Drop for PositiveImbalance<T, GetCurrencyId> {
 	/// Basic drop handler will just square up the total issuance.
 	fn drop(&mut self) {
-		TotalIssuance::<T>::mutate(GetCurrencyId::get(), |v| *v = v.saturating_add(self.0));
+		Pallet::<T>::mutate_total_issuance(GetCurrencyId::get(), |v| *v = v.saturating_add(self.0));
 	}
 }
 
 impl<T: Config, GetCurrencyId: Get<T::CurrencyId>> Drop for NegativeImbalance<T, GetCurrencyId> {
 	/// Basic drop handler will just square up the total issuance.
 	fn drop(&mut self) {
-		TotalIssuance::<T>::mutate(GetCurrencyId::get(), |v| *v = v.saturating_sub(self.0));
+		Pallet::<T>::mutate_total_issuance(GetCurrencyId::get(), |v| *v = v.saturating_sub(self.0));
+	}
+}
+
+/// Opaque, move-only struct with private fields that serves as a token
+/// denoting that funds of `currency_id` have been created without any equal
+/// and opposite accounting.
+#[must_use]
+pub struct MultiPositiveImbalance<T: Config> {
+	currency_id: T::CurrencyId,
+	amount: T::Balance,
+}
+
+impl<T: Config> MultiPositiveImbalance<T> {
+	/// Create a new positive imbalance of `currency_id` from a balance.
+	pub fn new(currency_id: T::CurrencyId, amount: T::Balance) -> Self {
+		MultiPositiveImbalance { currency_id, amount }
+	}
+}
+
+/// Opaque, move-only struct with private fields that serves as a token
+/// denoting that funds of `currency_id` have been destroyed without any equal
+/// and opposite accounting.
+#[must_use]
+pub struct MultiNegativeImbalance<T: Config> {
+	currency_id: T::CurrencyId,
+	amount: T::Balance,
+}
+
+impl<T: Config> MultiNegativeImbalance<T> {
+	/// Create a new negative imbalance of `currency_id` from a balance.
+	pub fn new(currency_id: T::CurrencyId, amount: T::Balance) -> Self {
+		MultiNegativeImbalance { currency_id, amount }
+	}
+}
+
+impl<T: Config> TryDrop for MultiPositiveImbalance<T> {
+	fn try_drop(self) -> result::Result<(), Self> {
+		if self.amount.is_zero() {
+			Ok(())
+		} else {
+			Err(self)
+		}
+	}
+}
+
+impl<T: Config> MultiImbalance<T::CurrencyId, T::Balance> for MultiPositiveImbalance<T> {
+	type Opposite = MultiNegativeImbalance<T>;
+
+	fn zero(currency_id: T::CurrencyId) -> Self {
+		Self::new(currency_id, Zero::zero())
+	}
+	fn currency_id(&self) -> T::CurrencyId {
+		self.currency_id
+	}
+	fn peek(&self) -> T::Balance {
+		self.amount
+	}
+	fn split(self, amount: T::Balance) -> (Self, Self) {
+		let (currency_id, first) = (self.currency_id, self.amount.min(amount));
+		let second = self.amount.saturating_sub(first);
+
+		mem::forget(self);
+		(Self::new(currency_id, first), Self::new(currency_id, second))
+	}
+	fn merge(mut self, other: Self) -> result::Result<Self, (Self, Self)> {
+		if self.currency_id != other.currency_id {
+			return Err((self, other));
+		}
+		self.amount = self.amount.saturating_add(other.amount);
+		mem::forget(other);
+
+		Ok(self)
+	}
+	fn subsume(&mut self, other: Self) -> result::Result<(), Self> {
+		if self.currency_id != other.currency_id {
+			return Err(other);
+		}
+		self.amount = self.amount.saturating_add(other.amount);
+		mem::forget(other);
+
+		Ok(())
+	}
+	// allow to make the impl same with `pallet-balances`
+	#[allow(clippy::comparison_chain)]
+	#[allow(clippy::type_complexity)]
+	fn offset(
+		self,
+		other: Self::Opposite,
+	) -> result::Result<SameOrOther<Self, Self::Opposite>, (Self, Self::Opposite)> {
+		if self.currency_id != other.currency_id {
+			return Err((self, other));
+		}
+		let (currency_id, a, b) = (self.currency_id, self.amount, other.amount);
+		mem::forget((self, other));
+
+		Ok(if a > b {
+			SameOrOther::Same(Self::new(currency_id, a.saturating_sub(b)))
+		} else if b > a {
+			SameOrOther::Other(MultiNegativeImbalance::new(currency_id, b.saturating_sub(a)))
+		} else {
+			SameOrOther::None
+		})
+	}
+}
+
+impl<T: Config> TryDrop for MultiNegativeImbalance<T> {
+	fn try_drop(self) -> result::Result<(), Self> {
+		if self.amount.is_zero() {
+			Ok(())
+		} else {
+			Err(self)
+		}
+	}
+}
+
+impl<T: Config> MultiImbalance<T::CurrencyId, T::Balance> for MultiNegativeImbalance<T> {
+	type Opposite = MultiPositiveImbalance<T>;
+
+	fn zero(currency_id: T::CurrencyId) -> Self {
+		Self::new(currency_id, Zero::zero())
+	}
+	fn currency_id(&self) -> T::CurrencyId {
+		self.currency_id
+	}
+	fn peek(&self) -> T::Balance {
+		self.amount
+	}
+	fn split(self, amount: T::Balance) -> (Self, Self) {
+		let (currency_id, first) = (self.currency_id, self.amount.min(amount));
+		let second = self.amount.saturating_sub(first);
+
+		mem::forget(self);
+		(Self::new(currency_id, first), Self::new(currency_id, second))
+	}
+	fn merge(mut self, other: Self) -> result::Result<Self, (Self, Self)> {
+		if self.currency_id != other.currency_id {
+			return Err((self, other));
+		}
+		self.amount = self.amount.saturating_add(other.amount);
+		mem::forget(other);
+
+		Ok(self)
+	}
+	fn subsume(&mut self, other: Self) -> result::Result<(), Self> {
+		if self.currency_id != other.currency_id {
+			return Err(other);
+		}
+		self.amount = self.amount.saturating_add(other.amount);
+		mem::forget(other);
+
+		Ok(())
+	}
+	// allow to make the impl same with `pallet-balances`
+	#[allow(clippy::comparison_chain)]
+	#[allow(clippy::type_complexity)]
+	fn offset(
+		self,
+		other: Self::Opposite,
+	) -> result::Result<SameOrOther<Self, Self::Opposite>, (Self, Self::Opposite)> {
+		if self.currency_id != other.currency_id {
+			return Err((self, other));
+		}
+		let (currency_id, a, b) = (self.currency_id, self.amount, other.amount);
+		mem::forget((self, other));
+
+		Ok(if a > b {
+			SameOrOther::Same(Self::new(currency_id, a.saturating_sub(b)))
+		} else if b > a {
+			SameOrOther::Other(MultiPositiveImbalance::new(currency_id, b.saturating_sub(a)))
+		} else {
+			SameOrOther::None
+		})
+	}
+}
+
+impl<T: Config> Drop for MultiPositiveImbalance<T> {
+	/// Basic drop handler will just square up the total issuance.
+	fn drop(&mut self) {
+		Pallet::<T>::mutate_total_issuance(self.currency_id, |v| *v = v.saturating_add(self.amount));
+	}
+}
+
+impl<T: Config> Drop for MultiNegativeImbalance<T> {
+	/// Basic drop handler will just square up the total issuance.
+	fn drop(&mut self) {
+		Pallet::<T>::mutate_total_issuance(self.currency_id, |v| *v = v.saturating_sub(self.amount));
 	}
 }
@@ -1,11 +1,14 @@
-use frame_support::traits::tokens::{Fortitude, Precision, Preservation, Provenance};
+use frame_support::traits::tokens::{Fortitude, Precision, Preservation, Provenance, Restriction};
 use frame_support::traits::{
 	fungible, fungibles,
 	tokens::{Balance as BalanceT, DepositConsequence, WithdrawConsequence},
 	Contains, Get,
 };
-use sp_arithmetic::{traits::Bounded, ArithmeticError};
-use sp_runtime::DispatchError;
+use orml_traits::asset_registry;
+use sp_arithmetic::{
+	helpers_128bit::multiply_by_rational_with_rounding, per_things::Rounding, traits::Bounded, ArithmeticError,
+};
+use sp_runtime::{DispatchError, DispatchResult};
 
 pub struct Combiner<AccountId, TestKey, A, B>(sp_std::marker::PhantomData<(AccountId, TestKey, A, B)>);
 
@@ -176,6 +179,197 @@ where
 	}
 }
 
+impl<AccountId, TestKey, A, B> fungibles::InspectHold<AccountId> for Combiner<AccountId, TestKey, A, B>
+where
+	TestKey: Contains<<B as fungibles::Inspect<AccountId>>::AssetId>,
+	A: fungible::InspectHold<
+		AccountId,
+		Balance = <B as fungibles::Inspect<AccountId>>::Balance,
+		Reason = <B as fungibles::InspectHold<AccountId>>::Reason,
+	>,
+	B: fungibles::InspectHold<AccountId>,
+{
+	type Reason = <B as fungibles::InspectHold<AccountId>>::Reason;
+
+	fn total_balance_on_hold(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		if TestKey::contains(&asset) {
+			A::total_balance_on_hold(who)
+		} else {
+			B::total_balance_on_hold(asset, who)
+		}
+	}
+
+	fn reducible_total_balance_on_hold(asset: Self::AssetId, who: &AccountId, force: Fortitude) -> Self::Balance {
+		if TestKey::contains(&asset) {
+			A::reducible_total_balance_on_hold(who, force)
+		} else {
+			B::reducible_total_balance_on_hold(asset, who, force)
+		}
+	}
+
+	fn balance_on_hold(asset: Self::AssetId, reason: &Self::Reason, who: &AccountId) -> Self::Balance {
+		if TestKey::contains(&asset) {
+			A::balance_on_hold(reason, who)
+		} else {
+			B::balance_on_hold(asset, reason, who)
+		}
+	}
+
+	fn hold_available(asset: Self::AssetId, reason: &Self::Reason, who: &AccountId) -> bool {
+		if TestKey::contains(&asset) {
+			A::hold_available(reason, who)
+		} else {
+			B::hold_available(asset, reason, who)
+		}
+	}
+
+	fn can_hold(asset: Self::AssetId, reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> bool {
+		if TestKey::contains(&asset) {
+			A::can_hold(reason, who, amount)
+		} else {
+			B::can_hold(asset, reason, who, amount)
+		}
+	}
+}
+
+impl<AccountId, TestKey, A, B> fungibles::MutateHold<AccountId> for Combiner<AccountId, TestKey, A, B>
+where
+	TestKey: Contains<<B as fungibles::Inspect<AccountId>>::AssetId>,
+	A: fungible::MutateHold<
+			AccountId,
+			Balance = <B as fungibles::Inspect<AccountId>>::Balance,
+			Reason = <B as fungibles::InspectHold<AccountId>>::Reason,
+		> + fungible::Mutate<AccountId>,
+	B: fungibles::MutateHold<AccountId> + fungibles::Mutate<AccountId>,
+{
+	fn hold(asset: Self::AssetId, reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		if TestKey::contains(&asset) {
+			A::hold(reason, who, amount)
+		} else {
+			B::hold(asset, reason, who, amount)
+		}
+	}
+
+	fn release(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		if TestKey::contains(&asset) {
+			A::release(reason, who, amount, precision)
+		} else {
+			B::release(asset, reason, who, amount, precision)
+		}
+	}
+
+	fn transfer_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		restriction: Restriction,
+		fortitude: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		if TestKey::contains(&asset) {
+			A::transfer_on_hold(reason, source, dest, amount, precision, restriction, fortitude)
+		} else {
+			B::transfer_on_hold(asset, reason, source, dest, amount, precision, restriction, fortitude)
+		}
+	}
+}
+
+impl<AccountId, TestKey, A, B> fungibles::UnbalancedHold<AccountId> for Combiner<AccountId, TestKey, A, B>
+where
+	TestKey: Contains<<B as fungibles::Inspect<AccountId>>::AssetId>,
+	A: fungible::UnbalancedHold<
+		AccountId,
+		Balance = <B as fungibles::Inspect<AccountId>>::Balance,
+		Reason = <B as fungibles::InspectHold<AccountId>>::Reason,
+	>,
+	B: fungibles::UnbalancedHold<AccountId>,
+{
+	fn set_balance_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if TestKey::contains(&asset) {
+			A::set_balance_on_hold(reason, who, amount)
+		} else {
+			B::set_balance_on_hold(asset, reason, who, amount)
+		}
+	}
+}
+
+impl<AccountId, TestKey, A, B> fungibles::InspectFreeze<AccountId> for Combiner<AccountId, TestKey, A, B>
+where
+	TestKey: Contains<<B as fungibles::Inspect<AccountId>>::AssetId>,
+	A: fungible::InspectFreeze<
+		AccountId,
+		Balance = <B as fungibles::Inspect<AccountId>>::Balance,
+		Id = <B as fungibles::InspectFreeze<AccountId>>::Id,
+	>,
+	B: fungibles::InspectFreeze<AccountId>,
+{
+	type Id = <B as fungibles::InspectFreeze<AccountId>>::Id;
+
+	fn balance_frozen(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> Self::Balance {
+		if TestKey::contains(&asset) {
+			A::balance_frozen(id, who)
+		} else {
+			B::balance_frozen(asset, id, who)
+		}
+	}
+
+	fn can_freeze(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> bool {
+		if TestKey::contains(&asset) {
+			A::can_freeze(id, who)
+		} else {
+			B::can_freeze(asset, id, who)
+		}
+	}
+}
+
+impl<AccountId, TestKey, A, B> fungibles::MutateFreeze<AccountId> for Combiner<AccountId, TestKey, A, B>
+where
+	TestKey: Contains<<B as fungibles::Inspect<AccountId>>::AssetId>,
+	A: fungible::MutateFreeze<
+		AccountId,
+		Balance = <B as fungibles::Inspect<AccountId>>::Balance,
+		Id = <B as fungibles::InspectFreeze<AccountId>>::Id,
+	>,
+	B: fungibles::MutateFreeze<AccountId>,
+{
+	fn set_freeze(asset: Self::AssetId, id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		if TestKey::contains(&asset) {
+			A::set_freeze(id, who, amount)
+		} else {
+			B::set_freeze(asset, id, who, amount)
+		}
+	}
+
+	fn extend_freeze(asset: Self::AssetId, id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		if TestKey::contains(&asset) {
+			A::extend_freeze(id, who, amount)
+		} else {
+			B::extend_freeze(asset, id, who, amount)
+		}
+	}
+
+	fn thaw(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> DispatchResult {
+		if TestKey::contains(&asset) {
+			A::thaw(id, who)
+		} else {
+			B::thaw(asset, id, who)
+		}
+	}
+}
+
 pub trait ConvertBalance<A: Bounded, B: Bounded> {
 	type AssetId;
 	fn convert_balance(amount: A, asset_id: Self::AssetId) -> Result<B, ArithmeticError>;
@@ -197,6 +391,76 @@ pub trait ConvertBalance<A: Bounded, B: Bounded> {
 	}
 }
 
+/// Converts balances between the decimals of an asset, as registered in the
+/// asset registry, and `TargetDecimals`.
+///
+/// Amounts are rounded with `RoundingMode` when the precision is reduced, so
+/// reducing it by more digits than a `u128` has yields zero, or one if
+/// rounding up a non-zero amount.
+///
+/// The decimals of assets without registered metadata are unknown, so their
+/// conversions fail with `ArithmeticError::Underflow` in both directions, and
+/// the saturating conversions treat their balances as zero rather than
+/// guessing the decimals.
+pub struct DecimalsConvertBalance<Registry, TargetDecimals, RoundingMode>(
+	sp_std::marker::PhantomData<(Registry, TargetDecimals, RoundingMode)>,
+);
+
+impl<Registry, TargetDecimals, RoundingMode> DecimalsConvertBalance<Registry, TargetDecimals, RoundingMode>
+where
+	Registry: asset_registry::Inspect,
+	RoundingMode: Get<Rounding>,
+{
+	fn decimals(asset_id: &Registry::AssetId) -> Result<u32, ArithmeticError> {
+		Registry::metadata(asset_id)
+			.map(|metadata| metadata.decimals)
+			.ok_or(ArithmeticError::Underflow)
+	}
+
+	fn rescale<FromBalance: BalanceT, ToBalance: BalanceT>(
+		amount: FromBalance,
+		from: u32,
+		to: u32,
+	) -> Result<ToBalance, ArithmeticError> {
+		let amount = TryInto::<u128>::try_into(amount).map_err(|_| ArithmeticError::Overflow)?;
+		let rescaled = if from <= to {
+			10u128
+				.checked_pow(to - from)
+				.and_then(|factor| amount.checked_mul(factor))
+				.ok_or(ArithmeticError::Overflow)?
+		} else {
+			match 10u128.checked_pow(from - to) {
+				Some(factor) => multiply_by_rational_with_rounding(amount, 1, factor, RoundingMode::get())
+					.ok_or(ArithmeticError::Overflow)?,
+				// the factor is more than twice any amount, so only rounding up keeps anything
+				None if amount > 0 && matches!(RoundingMode::get(), Rounding::Up) => 1,
+				None => 0,
+			}
+		};
+		ToBalance::try_from(rescaled).map_err(|_| ArithmeticError::Overflow)
+	}
+}
+
+impl<A, B, Registry, TargetDecimals, RoundingMode> ConvertBalance<A, B>
+	for DecimalsConvertBalance<Registry, TargetDecimals, RoundingMode>
+where
+	A: BalanceT,
+	B: BalanceT,
+	Registry: asset_registry::Inspect,
+	TargetDecimals: Get<u32>,
+	RoundingMode: Get<Rounding>,
+{
+	type AssetId = Registry::AssetId;
+
+	fn convert_balance(amount: A, asset_id: Self::AssetId) -> Result<B, ArithmeticError> {
+		Self::rescale(amount, Self::decimals(&asset_id)?, TargetDecimals::get())
+	}
+
+	fn convert_balance_back(amount: B, asset_id: Self::AssetId) -> Result<A, ArithmeticError> {
+		Self::rescale(amount, TargetDecimals::get(), Self::decimals(&asset_id)?)
+	}
+}
+
 pub struct Mapper<AccountId, T, C, B, GetCurrencyId>(sp_std::marker::PhantomData<(AccountId, T, C, B, GetCurrencyId)>);
 impl<AccountId, T, C, B, GetCurrencyId> fungible::Inspect<AccountId> for Mapper<AccountId, T, C, B, GetCurrencyId>
 where
@@ -344,3 +608,186 @@ where
 		T::set_total_issuance(GetCurrencyId::get(), amount)
 	}
 }
+
+impl<AccountId, T, C, B, GetCurrencyId> fungible::InspectHold<AccountId> for Mapper<AccountId, T, C, B, GetCurrencyId>
+where
+	T: fungibles::InspectHold<AccountId>,
+	C: ConvertBalance<
+		<T as fungibles::Inspect<AccountId>>::Balance,
+		B,
+		AssetId = <T as fungibles::Inspect<AccountId>>::AssetId,
+	>,
+	B: BalanceT,
+	GetCurrencyId: Get<<T as fungibles::Inspect<AccountId>>::AssetId>,
+{
+	type Reason = <T as fungibles::InspectHold<AccountId>>::Reason;
+
+	fn total_balance_on_hold(who: &AccountId) -> Self::Balance {
+		C::convert_balance_saturated(
+			T::total_balance_on_hold(GetCurrencyId::get(), who),
+			GetCurrencyId::get(),
+		)
+	}
+
+	fn reducible_total_balance_on_hold(who: &AccountId, force: Fortitude) -> Self::Balance {
+		C::convert_balance_saturated(
+			T::reducible_total_balance_on_hold(GetCurrencyId::get(), who, force),
+			GetCurrencyId::get(),
+		)
+	}
+
+	fn balance_on_hold(reason: &Self::Reason, who: &AccountId) -> Self::Balance {
+		C::convert_balance_saturated(
+			T::balance_on_hold(GetCurrencyId::get(), reason, who),
+			GetCurrencyId::get(),
+		)
+	}
+
+	fn hold_available(reason: &Self::Reason, who: &AccountId) -> bool {
+		T::hold_available(GetCurrencyId::get(), reason, who)
+	}
+
+	fn can_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> bool {
+		match C::convert_balance_back(amount, GetCurrencyId::get()) {
+			Ok(amount) => T::can_hold(GetCurrencyId::get(), reason, who, amount),
+			Err(_) => false,
+		}
+	}
+}
+
+impl<AccountId, T, C, B, GetCurrencyId> fungible::MutateHold<AccountId> for Mapper<AccountId, T, C, B, GetCurrencyId>
+where
+	T: fungibles::MutateHold<AccountId, Balance = B> + fungibles::Mutate<AccountId, Balance = B>,
+	C: ConvertBalance<
+		<T as fungibles::Inspect<AccountId>>::Balance,
+		B,
+		AssetId = <T as fungibles::Inspect<AccountId>>::AssetId,
+	>,
+	B: BalanceT,
+	GetCurrencyId: Get<<T as fungibles::Inspect<AccountId>>::AssetId>,
+{
+	fn hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		T::hold(
+			GetCurrencyId::get(),
+			reason,
+			who,
+			C::convert_balance_back(amount, GetCurrencyId::get())?,
+		)
+	}
+
+	fn release(
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		let released = T::release(
+			GetCurrencyId::get(),
+			reason,
+			who,
+			C::convert_balance_back(amount, GetCurrencyId::get())?,
+			precision,
+		)?;
+		Ok(C::convert_balance_saturated(released, GetCurrencyId::get()))
+	}
+
+	fn transfer_on_hold(
+		reason: &Self::Reason,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		restriction: Restriction,
+		fortitude: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		let transferred = T::transfer_on_hold(
+			GetCurrencyId::get(),
+			reason,
+			source,
+			dest,
+			C::convert_balance_back(amount, GetCurrencyId::get())?,
+			precision,
+			restriction,
+			fortitude,
+		)?;
+		Ok(C::convert_balance_saturated(transferred, GetCurrencyId::get()))
+	}
+}
+
+impl<AccountId, T, C, B, GetCurrencyId> fungible::UnbalancedHold<AccountId>
+	for Mapper<AccountId, T, C, B, GetCurrencyId>
+where
+	T: fungibles::UnbalancedHold<AccountId, Balance = B> + fungibles::Unbalanced<AccountId, Balance = B>,
+	C: ConvertBalance<
+		<T as fungibles::Inspect<AccountId>>::Balance,
+		B,
+		AssetId = <T as fungibles::Inspect<AccountId>>::AssetId,
+	>,
+	B: BalanceT,
+	GetCurrencyId: Get<<T as fungibles::Inspect<AccountId>>::AssetId>,
+{
+	fn set_balance_on_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		T::set_balance_on_hold(
+			GetCurrencyId::get(),
+			reason,
+			who,
+			C::convert_balance_back(amount, GetCurrencyId::get())?,
+		)
+	}
+}
+
+impl<AccountId, T, C, B, GetCurrencyId> fungible::InspectFreeze<AccountId> for Mapper<AccountId, T, C, B, GetCurrencyId>
+where
+	T: fungibles::InspectFreeze<AccountId>,
+	C: ConvertBalance<
+		<T as fungibles::Inspect<AccountId>>::Balance,
+		B,
+		AssetId = <T as fungibles::Inspect<AccountId>>::AssetId,
+	>,
+	B: BalanceT,
+	GetCurrencyId: Get<<T as fungibles::Inspect<AccountId>>::AssetId>,
+{
+	type Id = <T as fungibles::InspectFreeze<AccountId>>::Id;
+
+	fn balance_frozen(id: &Self::Id, who: &AccountId) -> Self::Balance {
+		C::convert_balance_saturated(T::balance_frozen(GetCurrencyId::get(), id, who), GetCurrencyId::get())
+	}
+
+	fn can_freeze(id: &Self::Id, who: &AccountId) -> bool {
+		T::can_freeze(GetCurrencyId::get(), id, who)
+	}
+}
+
+impl<AccountId, T, C, B, GetCurrencyId> fungible::MutateFreeze<AccountId> for Mapper<AccountId, T, C, B, GetCurrencyId>
+where
+	T: fungibles::MutateFreeze<AccountId>,
+	C: ConvertBalance<
+		<T as fungibles::Inspect<AccountId>>::Balance,
+		B,
+		AssetId = <T as fungibles::Inspect<AccountId>>::AssetId,
+	>,
+	B: BalanceT,
+	GetCurrencyId: Get<<T as fungibles::Inspect<AccountId>>::AssetId>,
+{
+	fn set_freeze(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		T::set_freeze(
+			GetCurrencyId::get(),
+			id,
+			who,
+			C::convert_balance_back(amount, GetCurrencyId::get())?,
+		)
+	}
+
+	fn extend_freeze(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		T::extend_freeze(
+			GetCurrencyId::get(),
+			id,
+			who,
+			C::convert_balance_back(amount, GetCurrencyId::get())?,
+		)
+	}
+
+	fn thaw(id: &Self::Id, who: &AccountId) -> DispatchResult {
+		T::thaw(GetCurrencyId::get(), id, who)
+	}
+}
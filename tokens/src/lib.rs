@@ -28,6 +28,29 @@
 //!
 //! - `transfer` - Transfer some balance to another account.
 //! - `transfer_all` - Transfer all balance to another account.
+//! - `transfer_batch` - Transfer balances of multiple currencies to multiple
+//!   accounts at once.
+//! - `reap_dust_accounts` - Remove the dust of accounts which are below the
+//!   existential deposit.
+//! - `approve_transfer` - Approve a delegated account to transfer some
+//!   balance.
+//! - `cancel_approval` - Cancel an approval, returning its deposit.
+//! - `transfer_approved` - Transfer some balance of an account that approved
+//!   the caller.
+//! - `freeze_currency` / `thaw_currency` - Pause or resume all transfers of a
+//!   currency.
+//! - `freeze_account` / `thaw_account` - Pause or resume transfers of a
+//!   currency out of an account.
+//! - `set_compliance_policy` - Restrict which accounts may hold and move a
+//!   currency.
+//! - `add_compliance_member` / `remove_compliance_member` - Manage the
+//!   accounts allowed or denied by the compliance policy of a currency.
+//! - `force_unreserve_named` / `force_repatriate_reserved_named` - Recover
+//!   named reserves, e.g. those left behind by removed pallets.
+//! - `transfer_all_currencies` - Transfer all balances of every currency, or
+//!   of the listed currencies, to another account.
+//! - `transfer_with_memo` - Transfer some balance to another account with a
+//!   memo attached.
 //!
 //! ### Genesis Config
 //!
@@ -38,11 +61,12 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::comparison_chain)]
 
-pub use crate::imbalances::{NegativeImbalance, PositiveImbalance};
+pub use crate::imbalances::{MultiNegativeImbalance, MultiPositiveImbalance, NegativeImbalance, PositiveImbalance};
 
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
+	storage::with_storage_layer,
 	traits::{
 		tokens::{
 			fungible, fungibles, DepositConsequence, Fortitude, Precision, Preservation, Provenance, Restriction,
@@ -65,27 +89,40 @@ use sp_runtime::{
 	},
 	ArithmeticError, DispatchError, DispatchResult, FixedPointOperand, RuntimeDebug, TokenError,
 };
-use sp_std::{cmp, convert::Infallible, marker, prelude::*, vec::Vec};
+use sp_std::{
+	cmp,
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	convert::Infallible,
+	marker,
+	prelude::*,
+	vec::Vec,
+};
 
 use orml_traits::{
 	arithmetic::{self, Signed},
 	currency::{MutationHooks, OnDeposit, OnDust, OnSlash, OnTransfer, TransferAll},
-	BalanceStatus, GetByKey, Happened, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
-	MultiReservableCurrency, NamedMultiReservableCurrency,
+	BalanceStatus, GetByKey, Happened, LockIdentifier, MultiCurrency, MultiCurrencyExtended,
+	MultiExpiringLockableCurrency, MultiImbalance, MultiLockableCurrency, MultiReservableCurrency,
+	NamedMultiReservableCurrency,
 };
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod imbalances;
 mod impls;
+pub mod migrations;
 mod mock;
 mod tests;
 mod tests_currency_adapter;
 mod tests_events;
 mod tests_fungibles;
+mod tests_migrations;
 mod tests_multicurrency;
 
 mod weights;
 
 pub use impls::*;
+use migrations::MultiBlockMigration;
 pub use weights::WeightInfo;
 
 pub struct TransferDust<T, GetAccountId>(marker::PhantomData<(T, GetAccountId)>);
@@ -116,16 +153,58 @@ impl<T: Config> OnDust<T::AccountId, T::CurrencyId, T::Balance> for BurnDust<T>
 	}
 }
 
+/// The reasons for which a lock prevents withdrawals, stored as the bits of
+/// `WithdrawReasons`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct LockReasons(u8);
+
+impl LockReasons {
+	/// Whether a withdrawal for any of `reasons` is prevented.
+	pub fn intersects(&self, reasons: WithdrawReasons) -> bool {
+		WithdrawReasons::from(*self).intersects(reasons)
+	}
+}
+
+impl Default for LockReasons {
+	fn default() -> Self {
+		WithdrawReasons::all().into()
+	}
+}
+
+impl From<WithdrawReasons> for LockReasons {
+	fn from(reasons: WithdrawReasons) -> Self {
+		LockReasons(reasons.bits())
+	}
+}
+
+impl From<LockReasons> for WithdrawReasons {
+	fn from(reasons: LockReasons) -> Self {
+		WithdrawReasons::from_bits_truncate(reasons.0)
+	}
+}
+
 /// A single lock on a balance. There can be many of these on an account and
 /// they "overlap", so the same balance is frozen by multiple locks.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
-pub struct BalanceLock<Balance> {
+pub struct BalanceLock<Balance, BlockNumber> {
 	/// An identifier for this lock. Only one lock may be in existence for
 	/// each identifier.
 	pub id: LockIdentifier,
 	/// The amount which the free balance may not drop below when this lock
 	/// is in effect.
 	pub amount: Balance,
+	/// The block at which this lock stops being in effect, `None` if the lock
+	/// lives until it is removed.
+	pub expires_at: Option<BlockNumber>,
+	/// The withdrawals this lock prevents.
+	pub reasons: LockReasons,
+}
+
+impl<Balance, BlockNumber: PartialOrd> BalanceLock<Balance, BlockNumber> {
+	/// Whether this lock is no longer in effect at block `now`.
+	pub fn is_expired(&self, now: &BlockNumber) -> bool {
+		self.expires_at.as_ref().is_some_and(|expires_at| expires_at <= now)
+	}
 }
 
 /// Store named reserved balance.
@@ -137,6 +216,46 @@ pub struct ReserveData<ReserveIdentifier, Balance> {
 	pub amount: Balance,
 }
 
+/// An approval for a delegated account to transfer some balance of the
+/// owner.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Approval<Balance> {
+	/// The amount that the delegated account is allowed to transfer.
+	pub amount: Balance,
+	/// The amount reserved from the owner for this approval.
+	pub deposit: Balance,
+}
+
+/// An identifier and balance.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct IdAmount<Id, Balance> {
+	/// An identifier for this item.
+	pub id: Id,
+	/// Some amount for this item.
+	pub amount: Balance,
+}
+
+/// The balance at the end of a block.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Checkpoint<BlockNumber, Balance> {
+	/// The block number.
+	pub block_number: BlockNumber,
+	/// The balance at the end of the block.
+	pub balance: Balance,
+}
+
+/// Which accounts may hold and move a currency.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum CompliancePolicy {
+	/// Any account.
+	#[default]
+	Open,
+	/// Only the compliance members of the currency.
+	Allowlist,
+	/// Any account except the compliance members of the currency.
+	Denylist,
+}
+
 /// balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
 pub struct AccountData<Balance> {
@@ -172,6 +291,16 @@ impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
 	}
 }
 
+/// The recipients, currency types and amounts of a `transfer_batch` call.
+pub type BatchTransfersOf<T> = BoundedVec<
+	(
+		<<T as frame_system::Config>::Lookup as StaticLookup>::Source,
+		<T as Config>::CurrencyId,
+		<T as Config>::Balance,
+	),
+	<T as Config>::MaxBatchTransfers,
+>;
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -231,11 +360,84 @@ pub mod module {
 		type MaxReserves: Get<u32>;
 
 		/// The id type for named reserves.
-		type ReserveIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
+		type ReserveIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy + MaybeSerializeDeserialize;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// The maximum number of holds that can exist on an account for a
+		/// currency.
+		#[pallet::constant]
+		type MaxHolds: Get<u32>;
+
+		/// The id type for freezes.
+		type FreezeIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// The maximum number of freezes that can exist on an account for a
+		/// currency.
+		#[pallet::constant]
+		type MaxFreezes: Get<u32>;
 
 		// The whitelist of accounts that will not be reaped even if its total
 		// is zero or below ED.
 		type DustRemovalWhitelist: Contains<Self::AccountId>;
+
+		/// The maximum total issuance of a currency, `None` if the currency is
+		/// uncapped.
+		type MaxTotalIssuance: GetByKey<Self::CurrencyId, Option<Self::Balance>>;
+
+		/// The amount reserved from the owner when creating a new approval.
+		type ApprovalDeposits: GetByKey<Self::CurrencyId, Self::Balance>;
+
+		/// The origin which may freeze and thaw currencies and accounts.
+		type FreezeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of transfers in a single `transfer_batch` call.
+		#[pallet::constant]
+		type MaxBatchTransfers: Get<u32>;
+
+		/// Migrations which run over multiple blocks in `on_idle`, see
+		/// [`migrations::MigrateToTokens`].
+		type MultiBlockMigrations: MultiBlockMigration<BlockNumberFor<Self>>;
+
+		/// The maximum number of accounts in a single `reap_dust_accounts` call.
+		#[pallet::constant]
+		type MaxReapAccounts: Get<u32>;
+
+		/// The currencies of which the history of balances and total issuance
+		/// is recorded. A currency shouldn't be added back once removed, its
+		/// history would miss the changes in between.
+		type CheckpointCurrencies: Contains<Self::CurrencyId>;
+
+		/// The maximum number of checkpoints kept per account or currency, the
+		/// oldest checkpoints are removed first.
+		#[pallet::constant]
+		type MaxCheckpoints: Get<u32>;
+
+		/// The currencies of which an account is a provider of the
+		/// `frame_system` account. Accounts of the other currencies take a
+		/// consumer reference instead, so they can only be created for
+		/// accounts that are already kept alive by something else. Changing
+		/// it only affects the accounts created afterwards.
+		type IsSufficient: Contains<Self::CurrencyId>;
+
+		/// The origin which may set the compliance policies and members of
+		/// currencies.
+		type ComplianceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of currencies swept in a single
+		/// `transfer_all_currencies` call.
+		#[pallet::constant]
+		type MaxTransferAllCurrencies: Get<u32>;
+
+		/// The maximum length of the memo attached to a transfer by
+		/// `transfer_with_memo`.
+		#[pallet::constant]
+		type MaxMemoLength: Get<u32>;
+
+		/// A currency used by the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type GetBenchmarkCurrencyId: Get<Self::CurrencyId>;
 	}
 
 	#[pallet::error]
@@ -256,6 +458,33 @@ pub mod module {
 		DeadAccount,
 		// Number of named reserves exceed `T::MaxReserves`
 		TooManyReserves,
+		/// Number of holds exceed `T::MaxHolds`
+		TooManyHolds,
+		/// Number of freezes exceed `T::MaxFreezes`
+		TooManyFreezes,
+		/// No approval exists that would allow the transfer
+		Unapproved,
+		/// The currency is frozen
+		CurrencyFrozen,
+		/// The account is frozen for the currency
+		AccountFrozen,
+		/// The currency or account is already frozen
+		AlreadyFrozen,
+		/// The currency or account is not frozen
+		NotFrozen,
+		/// The total issuance would exceed the maximum of the currency
+		MaxTotalIssuanceExceeded,
+		/// The compliance policy of the currency doesn't allow the account to
+		/// hold or move it
+		NotCompliant,
+		/// The account is already a compliance member of the currency
+		AlreadyComplianceMember,
+		/// The account is not a compliance member of the currency
+		NotComplianceMember,
+		/// The named reserve doesn't exist
+		NamedReserveNotFound,
+		/// The locks are being migrated to the current storage version
+		LocksMigrating,
 	}
 
 	#[pallet::event]
@@ -302,6 +531,32 @@ pub mod module {
 			amount: T::Balance,
 			status: BalanceStatus,
 		},
+		/// Some balance was reserved under a named reserve, in addition to
+		/// `Reserved`.
+		ReservedNamed {
+			id: T::ReserveIdentifier,
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+			amount: T::Balance,
+		},
+		/// Some balance was unreserved from a named reserve, in addition to
+		/// `Unreserved`.
+		UnreservedNamed {
+			id: T::ReserveIdentifier,
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+			amount: T::Balance,
+		},
+		/// Some balance of a named reserve was repatriated to another account,
+		/// in addition to `ReserveRepatriated`.
+		ReserveRepatriatedNamed {
+			id: T::ReserveIdentifier,
+			currency_id: T::CurrencyId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance,
+			status: BalanceStatus,
+		},
 		/// A balance was set by root.
 		BalanceSet {
 			currency_id: T::CurrencyId,
@@ -358,6 +613,57 @@ pub mod module {
 			who: T::AccountId,
 			amount: T::Balance,
 		},
+		/// Some funds are frozen
+		FreezeSet {
+			freeze_id: T::FreezeIdentifier,
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+			amount: T::Balance,
+		},
+		/// Some frozen funds were thawed
+		FreezeRemoved {
+			freeze_id: T::FreezeIdentifier,
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// A delegated account was approved to transfer some balance of the
+		/// owner.
+		ApprovedTransfer {
+			currency_id: T::CurrencyId,
+			owner: T::AccountId,
+			delegate: T::AccountId,
+			amount: T::Balance,
+		},
+		/// An approval was cancelled.
+		ApprovalCancelled {
+			currency_id: T::CurrencyId,
+			owner: T::AccountId,
+			delegate: T::AccountId,
+		},
+		/// Some balance was transferred by a delegated account.
+		TransferredApproved {
+			currency_id: T::CurrencyId,
+			owner: T::AccountId,
+			delegate: T::AccountId,
+			destination: T::AccountId,
+			amount: T::Balance,
+		},
+		/// A batch of transfers succeeded.
+		BatchTransferred { from: T::AccountId, count: u32 },
+		/// All transfers of a currency were frozen.
+		CurrencyFrozen { currency_id: T::CurrencyId },
+		/// Transfers of a currency were thawed.
+		CurrencyThawed { currency_id: T::CurrencyId },
+		/// Transfers of a currency out of an account were frozen.
+		AccountFrozen {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// Transfers of a currency out of an account were thawed.
+		AccountThawed {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
 		Issued {
 			currency_id: T::CurrencyId,
 			amount: T::Balance,
@@ -366,6 +672,40 @@ pub mod module {
 			currency_id: T::CurrencyId,
 			amount: T::Balance,
 		},
+		/// The compliance policy of a currency was set.
+		CompliancePolicySet {
+			currency_id: T::CurrencyId,
+			policy: CompliancePolicy,
+		},
+		/// An account was added to the compliance members of a currency.
+		ComplianceMemberAdded {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// An account was removed from the compliance members of a currency.
+		ComplianceMemberRemoved {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// The transferable balance of a currency was swept by
+		/// `transfer_all_currencies`, `result` tells whether the transfer
+		/// succeeded.
+		CurrencySwept {
+			currency_id: T::CurrencyId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance,
+			result: DispatchResult,
+		},
+		/// A memo was attached to a transfer, deposited right after its
+		/// `Transfer` event.
+		TransferMemo {
+			currency_id: T::CurrencyId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance,
+			memo: BoundedVec<u8, T::MaxMemoLength>,
+		},
 	}
 
 	/// The total issuance of a token type.
@@ -383,7 +723,7 @@ pub mod module {
 		T::AccountId,
 		Twox64Concat,
 		T::CurrencyId,
-		BoundedVec<BalanceLock<T::Balance>, T::MaxLocks>,
+		BoundedVec<BalanceLock<T::Balance, BlockNumberFor<T>>, T::MaxLocks>,
 		ValueQuery,
 	>;
 
@@ -405,6 +745,14 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// The accounts which took a consumer reference rather than a provider
+	/// reference when they were created, because their currency wasn't
+	/// sufficient then. The reference is released according to this, even if
+	/// `IsSufficient` changed since.
+	#[pallet::storage]
+	pub type ConsumerAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
 	/// Named reserves on some account balances.
 	#[pallet::storage]
 	#[pallet::getter(fn reserves)]
@@ -418,26 +766,143 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// Holds on account balances.
+	#[pallet::storage]
+	#[pallet::getter(fn holds)]
+	pub type Holds<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<IdAmount<T::RuntimeHoldReason, T::Balance>, T::MaxHolds>,
+		ValueQuery,
+	>;
+
+	/// Freezes on account balances.
+	#[pallet::storage]
+	#[pallet::getter(fn freezes)]
+	pub type Freezes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<IdAmount<T::FreezeIdentifier, T::Balance>, T::MaxFreezes>,
+		ValueQuery,
+	>;
+
+	/// Approved balance transfers. First key is the owner, second is the
+	/// currency and third is the delegated account.
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	pub type Approvals<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Twox64Concat, T::CurrencyId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		Approval<T::Balance>,
+		OptionQuery,
+	>;
+
+	/// Currencies of which all transfers and withdrawals are frozen.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_currencies)]
+	pub type FrozenCurrencies<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
+	/// Accounts of which transfers and withdrawals of a currency are frozen.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_accounts)]
+	pub type FrozenAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
+	/// The history of the total balance of accounts, for the currencies in
+	/// `CheckpointCurrencies`.
+	#[pallet::storage]
+	#[pallet::getter(fn balance_checkpoints)]
+	pub type BalanceCheckpoints<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<Checkpoint<BlockNumberFor<T>, T::Balance>, T::MaxCheckpoints>,
+		ValueQuery,
+	>;
+
+	/// The block from which on the history of a currency in
+	/// `CheckpointCurrencies` is recorded, i.e. the block of its first change.
+	/// The balances and total issuance before it are unknown.
+	#[pallet::storage]
+	#[pallet::getter(fn checkpoint_starts)]
+	pub type CheckpointStarts<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, BlockNumberFor<T>, OptionQuery>;
+
+	/// The history of the total issuance, for the currencies in
+	/// `CheckpointCurrencies`.
+	#[pallet::storage]
+	#[pallet::getter(fn total_issuance_checkpoints)]
+	pub type TotalIssuanceCheckpoints<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<Checkpoint<BlockNumberFor<T>, T::Balance>, T::MaxCheckpoints>,
+		ValueQuery,
+	>;
+
+	/// The compliance policy of a currency.
+	#[pallet::storage]
+	#[pallet::getter(fn compliance_policies)]
+	pub type CompliancePolicies<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, CompliancePolicy, ValueQuery>;
+
+	/// The compliance members of a currency, allowed by `Allowlist` and
+	/// denied by `Denylist`.
+	#[pallet::storage]
+	#[pallet::getter(fn compliance_members)]
+	pub type ComplianceMembers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::CurrencyId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// The raw `Locks` key from which `on_idle` resumes pruning expired
+	/// locks, empty to start from the beginning.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub(crate) type LockCleanupCursor<T: Config> = StorageValue<_, Vec<u8>, ValueQuery>;
+
 	#[pallet::genesis_config]
+	#[allow(clippy::type_complexity)]
 	pub struct GenesisConfig<T: Config> {
 		pub balances: Vec<(T::AccountId, T::CurrencyId, T::Balance)>,
+		/// Locks on endowed accounts.
+		pub locks: Vec<(T::AccountId, T::CurrencyId, LockIdentifier, T::Balance)>,
+		/// Named reserves on endowed accounts, which are endowed on top of
+		/// the free balance.
+		pub reserves: Vec<(T::AccountId, T::CurrencyId, T::ReserveIdentifier, T::Balance)>,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			GenesisConfig { balances: vec![] }
+			GenesisConfig {
+				balances: vec![],
+				locks: vec![],
+				reserves: vec![],
+			}
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
+			// the genesis locks have the current layout, `on_genesis` only puts the
+			// storage version after the genesis is built
+			STORAGE_VERSION.put::<Pallet<T>>();
+
 			// ensure no duplicates exist.
 			let unique_endowed_accounts = self
 				.balances
 				.iter()
 				.map(|(account_id, currency_id, _)| (account_id, currency_id))
-				.collect::<sp_std::collections::btree_set::BTreeSet<_>>();
+				.collect::<BTreeSet<_>>();
 			assert!(
 				unique_endowed_accounts.len() == self.balances.len(),
 				"duplicate endowed accounts in genesis."
@@ -459,14 +924,98 @@ pub mod module {
 							.expect("total issuance cannot overflow when building genesis")
 					});
 				});
+
+			let mut reserves = BTreeMap::<_, Vec<ReserveData<T::ReserveIdentifier, T::Balance>>>::new();
+			for (account_id, currency_id, id, amount) in self.reserves.iter() {
+				reserves
+					.entry((account_id, *currency_id))
+					.or_default()
+					.push(ReserveData {
+						id: *id,
+						amount: *amount,
+					});
+			}
+			for ((account_id, currency_id), mut reserves) in reserves {
+				assert!(
+					Accounts::<T>::contains_key(account_id, currency_id),
+					"named reserves can only be set on endowed accounts in genesis."
+				);
+				reserves.sort_by_key(|reserve| reserve.id);
+				assert!(
+					reserves.windows(2).all(|w| w[0].id != w[1].id),
+					"duplicate named reserves in genesis."
+				);
+				let reserved = reserves.iter().fold(Zero::zero(), |acc: T::Balance, reserve| {
+					acc.checked_add(&reserve.amount)
+						.expect("reserved balance cannot overflow when building genesis")
+				});
+				let reserves: BoundedVec<_, T::MaxReserves> = reserves
+					.try_into()
+					.expect("the number of named reserves should not exceed MaxReserves in genesis.");
+
+				Pallet::<T>::mutate_account(account_id, currency_id, |account_data, _| {
+					account_data.reserved = account_data
+						.reserved
+						.checked_add(&reserved)
+						.expect("reserved balance cannot overflow when building genesis");
+				});
+				Reserves::<T>::insert(account_id, currency_id, reserves);
+				TotalIssuance::<T>::mutate(currency_id, |total_issuance| {
+					*total_issuance = total_issuance
+						.checked_add(&reserved)
+						.expect("total issuance cannot overflow when building genesis")
+				});
+			}
+
+			let mut locks = BTreeMap::<_, Vec<BalanceLock<T::Balance, BlockNumberFor<T>>>>::new();
+			for (account_id, currency_id, id, amount) in self.locks.iter() {
+				locks.entry((account_id, *currency_id)).or_default().push(BalanceLock {
+					id: *id,
+					amount: *amount,
+					expires_at: None,
+					reasons: LockReasons::default(),
+				});
+			}
+			for ((account_id, currency_id), mut locks) in locks {
+				assert!(
+					Accounts::<T>::contains_key(account_id, currency_id),
+					"locks can only be set on endowed accounts in genesis."
+				);
+				locks.sort_by_key(|lock| lock.id);
+				assert!(
+					locks.windows(2).all(|w| w[0].id != w[1].id),
+					"duplicate locks in genesis."
+				);
+				assert!(
+					locks.len() <= T::MaxLocks::get() as usize,
+					"the number of locks should not exceed MaxLocks in genesis."
+				);
+				Pallet::<T>::update_locks(currency_id, account_id, &locks).expect("locks are valid in genesis");
+			}
 		}
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let used_weight = T::MultiBlockMigrations::on_idle(n, remaining_weight);
+			used_weight.saturating_add(Self::prune_expired_locks_on_idle(
+				remaining_weight.saturating_sub(used_weight),
+			))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -484,7 +1033,9 @@ pub mod module {
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::transfer())]
+		#[pallet::weight(
+			T::WeightInfo::transfer().saturating_add(Pallet::<T>::prune_expired_locks_weight(2))
+		)]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -516,7 +1067,9 @@ pub mod module {
 		///   except at least the existential deposit, which will guarantee to
 		///   keep the sender account alive (true).
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::transfer_all())]
+		#[pallet::weight(
+			T::WeightInfo::transfer_all().saturating_add(Pallet::<T>::prune_expired_locks_weight(2))
+		)]
 		pub fn transfer_all(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -552,7 +1105,9 @@ pub mod module {
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::transfer_keep_alive())]
+		#[pallet::weight(
+			T::WeightInfo::transfer_keep_alive().saturating_add(Pallet::<T>::prune_expired_locks_weight(2))
+		)]
 		pub fn transfer_keep_alive(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -575,7 +1130,9 @@ pub mod module {
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::force_transfer())]
+		#[pallet::weight(
+			T::WeightInfo::force_transfer().saturating_add(Pallet::<T>::prune_expired_locks_weight(2))
+		)]
 		pub fn force_transfer(
 			origin: OriginFor<T>,
 			source: <T::Lookup as StaticLookup>::Source,
@@ -598,7 +1155,9 @@ pub mod module {
 		///
 		/// The dispatch origin for this call is `root`.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::set_balance())]
+		#[pallet::weight(
+			T::WeightInfo::set_balance().saturating_add(Pallet::<T>::prune_expired_locks_weight(1))
+		)]
 		pub fn set_balance(
 			origin: OriginFor<T>,
 			who: <T::Lookup as StaticLookup>::Source,
@@ -623,14 +1182,14 @@ pub mod module {
 				account.reserved = new_reserved;
 
 				if new_total > old_total {
-					TotalIssuance::<T>::try_mutate(currency_id, |t| -> DispatchResult {
+					Self::try_mutate_total_issuance(currency_id, |t| -> DispatchResult {
 						*t = t
 							.checked_add(&(new_total.defensive_saturating_sub(old_total)))
 							.ok_or(ArithmeticError::Overflow)?;
 						Ok(())
 					})?;
 				} else if new_total < old_total {
-					TotalIssuance::<T>::try_mutate(currency_id, |t| -> DispatchResult {
+					Self::try_mutate_total_issuance(currency_id, |t| -> DispatchResult {
 						*t = t
 							.checked_sub(&(old_total.defensive_saturating_sub(new_total)))
 							.ok_or(ArithmeticError::Underflow)?;
@@ -649,289 +1208,1545 @@ pub mod module {
 
 			Ok(())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	pub(crate) fn deposit_consequence(
-		_who: &T::AccountId,
-		currency_id: T::CurrencyId,
-		amount: T::Balance,
-		account: &AccountData<T::Balance>,
-	) -> DepositConsequence {
-		if amount.is_zero() {
-			return DepositConsequence::Success;
+		/// Approve `delegate` to transfer up to `amount` of the sender's free
+		/// balance. If there is already an approval in place, `amount` is
+		/// added to it.
+		///
+		/// A deposit of `T::ApprovalDeposits` is reserved from the sender when
+		/// creating a new approval, and returned once the approval is
+		/// cancelled or used up.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		///
+		/// - `currency_id`: currency type.
+		/// - `delegate`: The account to delegate permission to transfer.
+		/// - `amount`: The amount that `delegate` is additionally allowed to
+		///   transfer.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::approve_transfer())]
+		pub fn approve_transfer(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			Self::do_approve_transfer(currency_id, &owner, &delegate, amount)
 		}
 
-		if TotalIssuance::<T>::get(currency_id).checked_add(&amount).is_none() {
-			return DepositConsequence::Overflow;
+		/// Cancel all of some approval for a delegated transfer, returning
+		/// the deposit to the sender.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		///
+		/// - `currency_id`: currency type.
+		/// - `delegate`: The account delegated permission to transfer.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::cancel_approval())]
+		pub fn cancel_approval(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			Self::do_cancel_approval(currency_id, &owner, &delegate)
 		}
 
-		let new_total_balance = match account.total().checked_add(&amount) {
-			Some(x) => x,
-			None => return DepositConsequence::Overflow,
-		};
-
-		if new_total_balance < T::ExistentialDeposits::get(&currency_id) {
-			return DepositConsequence::BelowMinimum;
+		/// Transfer some balance of `owner` that has been approved for the
+		/// sender.
+		///
+		/// The dispatch origin for this call must be `Signed` by the delegate.
+		///
+		/// - `owner`: The account which previously approved the sender.
+		/// - `currency_id`: currency type.
+		/// - `destination`: The recipient of the transfer.
+		/// - `amount`: free balance amount to transfer.
+		#[pallet::call_index(7)]
+		#[pallet::weight(
+			T::WeightInfo::transfer_approved().saturating_add(Pallet::<T>::prune_expired_locks_weight(2))
+		)]
+		pub fn transfer_approved(
+			origin: OriginFor<T>,
+			owner: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+			destination: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResult {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let destination = T::Lookup::lookup(destination)?;
+			Self::do_transfer_approved(currency_id, &owner, &delegate, &destination, amount)
 		}
 
-		// NOTE: We assume that we are a provider, so don't need to do any checks in the
-		// case of account creation.
+		/// Transfer some liquid free balance of multiple currencies to multiple
+		/// accounts.
+		///
+		/// Either all of the transfers succeed, or none of them does. A
+		/// `Transfer` event is emitted for each transfer, followed by a single
+		/// `BatchTransferred` event.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `transfers`: The recipients, currency types and free balance
+		///   amounts to transfer.
+		#[pallet::call_index(12)]
+		#[pallet::weight(
+			T::WeightInfo::transfer_batch(transfers.len() as u32)
+				.max(T::WeightInfo::transfer().saturating_mul(transfers.len() as u64))
+				.saturating_add(Pallet::<T>::prune_expired_locks_weight(transfers.len() as u32 + 1))
+		)]
+		pub fn transfer_batch(origin: OriginFor<T>, transfers: BatchTransfersOf<T>) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let count = transfers.len() as u32;
+			for (dest, currency_id, amount) in transfers.into_iter() {
+				let to = T::Lookup::lookup(dest)?;
+				Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+			}
+
+			Self::deposit_event(Event::BatchTransferred { from, count });
+			Ok(())
+		}
 
-		DepositConsequence::Success
-	}
+		/// Freeze all transfers and withdrawals of `currency_id`.
+		///
+		/// The dispatch origin for this call must be `T::FreezeOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::freeze_currency())]
+		pub fn freeze_currency(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			ensure!(
+				!FrozenCurrencies::<T>::contains_key(currency_id),
+				Error::<T>::AlreadyFrozen
+			);
+			FrozenCurrencies::<T>::insert(currency_id, ());
 
-	pub(crate) fn withdraw_consequence(
-		who: &T::AccountId,
-		currency_id: T::CurrencyId,
-		amount: T::Balance,
-		account: &AccountData<T::Balance>,
-	) -> WithdrawConsequence<T::Balance> {
-		if amount.is_zero() {
-			return WithdrawConsequence::Success;
+			Self::deposit_event(Event::CurrencyFrozen { currency_id });
+			Ok(())
 		}
 
-		if TotalIssuance::<T>::get(currency_id).checked_sub(&amount).is_none() {
-			return WithdrawConsequence::Underflow;
+		/// Thaw a currency frozen by `freeze_currency`.
+		///
+		/// The dispatch origin for this call must be `T::FreezeOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::thaw_currency())]
+		pub fn thaw_currency(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			ensure!(FrozenCurrencies::<T>::contains_key(currency_id), Error::<T>::NotFrozen);
+			FrozenCurrencies::<T>::remove(currency_id);
+
+			Self::deposit_event(Event::CurrencyThawed { currency_id });
+			Ok(())
 		}
 
-		let new_total_balance = match account.total().checked_sub(&amount) {
-			Some(x) => x,
-			None => return WithdrawConsequence::BalanceLow,
-		};
+		/// Freeze transfers and withdrawals of `currency_id` out of `who`.
+		/// The account can still receive funds.
+		///
+		/// The dispatch origin for this call must be `T::FreezeOrigin`.
+		///
+		/// - `who`: The account to be frozen.
+		/// - `currency_id`: currency type.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::freeze_account())]
+		pub fn freeze_account(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+		) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				!FrozenAccounts::<T>::contains_key(&who, currency_id),
+				Error::<T>::AlreadyFrozen
+			);
+			FrozenAccounts::<T>::insert(&who, currency_id, ());
+
+			Self::deposit_event(Event::AccountFrozen { currency_id, who });
+			Ok(())
+		}
+
+		/// Thaw an account frozen by `freeze_account`.
+		///
+		/// The dispatch origin for this call must be `T::FreezeOrigin`.
+		///
+		/// - `who`: The account to be thawed.
+		/// - `currency_id`: currency type.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::thaw_account())]
+		pub fn thaw_account(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+		) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				FrozenAccounts::<T>::contains_key(&who, currency_id),
+				Error::<T>::NotFrozen
+			);
+			FrozenAccounts::<T>::remove(&who, currency_id);
+
+			Self::deposit_event(Event::AccountThawed { currency_id, who });
+			Ok(())
+		}
+
+		/// Remove the dust of accounts whose total balance is below the
+		/// existential deposit of `currency_id`, e.g. after the existential
+		/// deposit was raised. The dust is handled by `OnDust`. Accounts which
+		/// are not below the existential deposit or are in
+		/// `DustRemovalWhitelist` are skipped.
+		///
+		/// The fee is refunded if all of the accounts were reaped.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `accounts`: The accounts to reap.
+		#[pallet::call_index(13)]
+		#[pallet::weight(
+			T::WeightInfo::reap_dust_accounts(accounts.len() as u32)
+				.saturating_add(Pallet::<T>::prune_expired_locks_weight(accounts.len() as u32))
+		)]
+		pub fn reap_dust_accounts(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			accounts: BoundedVec<T::AccountId, T::MaxReapAccounts>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let reaped = accounts
+				.iter()
+				.filter(|who| Self::reap_dust_account(currency_id, who))
+				.count();
+
+			if !accounts.is_empty() && reaped == accounts.len() {
+				Ok(Pays::No.into())
+			} else {
+				Ok(().into())
+			}
+		}
+
+		/// Set the compliance policy of `currency_id`, which is enforced on
+		/// transfers, deposits and repatriations of reserved balance.
+		///
+		/// The dispatch origin for this call must be `T::ComplianceOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `policy`: The new compliance policy.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::set_compliance_policy())]
+		pub fn set_compliance_policy(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			policy: CompliancePolicy,
+		) -> DispatchResult {
+			T::ComplianceOrigin::ensure_origin(origin)?;
+			CompliancePolicies::<T>::insert(currency_id, policy);
+
+			Self::deposit_event(Event::CompliancePolicySet { currency_id, policy });
+			Ok(())
+		}
+
+		/// Add `who` to the compliance members of `currency_id`.
+		///
+		/// The dispatch origin for this call must be `T::ComplianceOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `who`: The account to be added.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::add_compliance_member())]
+		pub fn add_compliance_member(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::ComplianceOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				!ComplianceMembers::<T>::contains_key(currency_id, &who),
+				Error::<T>::AlreadyComplianceMember
+			);
+			ComplianceMembers::<T>::insert(currency_id, &who, ());
+
+			Self::deposit_event(Event::ComplianceMemberAdded { currency_id, who });
+			Ok(())
+		}
+
+		/// Remove `who` from the compliance members of `currency_id`.
+		///
+		/// The dispatch origin for this call must be `T::ComplianceOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `who`: The account to be removed.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::remove_compliance_member())]
+		pub fn remove_compliance_member(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::ComplianceOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				ComplianceMembers::<T>::contains_key(currency_id, &who),
+				Error::<T>::NotComplianceMember
+			);
+			ComplianceMembers::<T>::remove(currency_id, &who);
+
+			Self::deposit_event(Event::ComplianceMemberRemoved { currency_id, who });
+			Ok(())
+		}
+
+		/// Unreserve up to `amount` from the named reserve `id` of `who`, e.g.
+		/// to recover a reserve left behind by a removed pallet.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `who`: The account whose reserve is released.
+		/// - `currency_id`: currency type.
+		/// - `id`: The identifier of the named reserve.
+		/// - `amount`: The amount to unreserve.
+		#[pallet::call_index(17)]
+		#[pallet::weight(
+			T::WeightInfo::force_unreserve_named().saturating_add(Pallet::<T>::prune_expired_locks_weight(1))
+		)]
+		pub fn force_unreserve_named(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+			id: T::ReserveIdentifier,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				Self::reserves(&who, currency_id)
+					.binary_search_by_key(&id, |data| data.id)
+					.is_ok(),
+				Error::<T>::NamedReserveNotFound
+			);
+			<Self as NamedMultiReservableCurrency<_>>::unreserve_named(&id, currency_id, &who, amount);
+			Ok(())
+		}
+
+		/// Move up to `amount` from the named reserve `id` of `slashed` to
+		/// `beneficiary`, according to `status`. If `status` is `Reserved`,
+		/// the balance is reserved under `id` on `beneficiary`.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `slashed`: The account whose reserve is moved.
+		/// - `beneficiary`: The recipient of the reserve.
+		/// - `currency_id`: currency type.
+		/// - `id`: The identifier of the named reserve.
+		/// - `amount`: The amount to move.
+		/// - `status`: Whether the balance is free or reserved on `beneficiary`.
+		#[pallet::call_index(18)]
+		#[pallet::weight(
+			T::WeightInfo::force_repatriate_reserved_named().saturating_add(Pallet::<T>::prune_expired_locks_weight(2))
+		)]
+		pub fn force_repatriate_reserved_named(
+			origin: OriginFor<T>,
+			slashed: <T::Lookup as StaticLookup>::Source,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+			id: T::ReserveIdentifier,
+			#[pallet::compact] amount: T::Balance,
+			status: BalanceStatus,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let slashed = T::Lookup::lookup(slashed)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(
+				Self::reserves(&slashed, currency_id)
+					.binary_search_by_key(&id, |data| data.id)
+					.is_ok(),
+				Error::<T>::NamedReserveNotFound
+			);
+			<Self as NamedMultiReservableCurrency<_>>::repatriate_reserved_named(
+				&id,
+				currency_id,
+				&slashed,
+				&beneficiary,
+				amount,
+				status,
+			)?;
+			Ok(())
+		}
+
+		/// Transfer all transferable balances of every currency of the
+		/// transactor to another account, up to `T::MaxTransferAllCurrencies`
+		/// currencies. Reserved and frozen balances are left behind.
+		///
+		/// Currencies are processed independently, a `CurrencySwept` event
+		/// reports the result of each non-zero transfer. Without
+		/// `currency_ids`, the first currencies of the account in storage
+		/// order are processed. Those which can't be swept, e.g. because their
+		/// balance is reserved or the transfer fails, stay first, so the other
+		/// currencies of an account with more currencies than the limit have to
+		/// be listed in `currency_ids`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `dest`: The recipient of the transfers.
+		/// - `keep_alive`: Whether at least the existential deposit of each
+		///   currency is kept, see [`transfer_all`].
+		/// - `currency_ids`: The currencies to sweep, or `None` for the first
+		///   currencies of the account.
+		#[pallet::call_index(19)]
+		#[pallet::weight({
+			let currencies = currency_ids
+				.as_ref()
+				.map_or(T::MaxTransferAllCurrencies::get(), |currency_ids| currency_ids.len() as u32);
+			T::WeightInfo::transfer_all_currencies(currencies)
+				.saturating_add(Pallet::<T>::prune_expired_locks_weight(2 * currencies))
+		})]
+		pub fn transfer_all_currencies(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			keep_alive: bool,
+			currency_ids: Option<BoundedVec<T::CurrencyId, T::MaxTransferAllCurrencies>>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let preservation = if keep_alive {
+				Preservation::Protect
+			} else {
+				Preservation::Expendable
+			};
+
+			// collect first, the accounts may be removed by the transfers.
+			let currency_ids: Vec<T::CurrencyId> = match currency_ids {
+				Some(currency_ids) => currency_ids.into_inner(),
+				None => Accounts::<T>::iter_key_prefix(&from)
+					.take(T::MaxTransferAllCurrencies::get() as usize)
+					.collect(),
+			};
+			for currency_id in currency_ids.iter().copied() {
+				let amount = <Self as fungibles::Inspect<T::AccountId>>::reducible_balance(
+					currency_id,
+					&from,
+					preservation,
+					Fortitude::Polite,
+				);
+				if amount.is_zero() {
+					continue;
+				}
+
+				let result = with_storage_layer(|| {
+					<Self as fungibles::Mutate<_>>::transfer(currency_id, &from, &to, amount, preservation).map(|_| ())
+				});
+				Self::deposit_event(Event::CurrencySwept {
+					currency_id,
+					from: from.clone(),
+					to: to.clone(),
+					amount,
+					result,
+				});
+			}
+
+			Ok(Some(T::WeightInfo::transfer_all_currencies(currency_ids.len() as u32)).into())
+		}
+
+		/// Transfer some liquid free balance to another account, attaching a
+		/// memo, e.g. an invoice id or deposit tag, to the transfer.
+		///
+		/// The memo is emitted in a `TransferMemo` event following the
+		/// `Transfer` event and isn't stored.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `dest`: The recipient of the transfer.
+		/// - `currency_id`: currency type.
+		/// - `amount`: free balance amount to tranfer.
+		/// - `memo`: The memo, at most `T::MaxMemoLength` bytes.
+		#[pallet::call_index(20)]
+		#[pallet::weight(
+			T::WeightInfo::transfer_with_memo().saturating_add(Pallet::<T>::prune_expired_locks_weight(2))
+		)]
+		pub fn transfer_with_memo(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+			#[pallet::compact] amount: T::Balance,
+			memo: BoundedVec<u8, T::MaxMemoLength>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+
+			Self::deposit_event(Event::TransferMemo {
+				currency_id,
+				from,
+				to,
+				amount,
+				memo,
+			});
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Remove the dust of `who` if its total balance is below the existential
+	/// deposit of `currency_id`. Returns `true` if the dust was removed.
+	pub(crate) fn reap_dust_account(currency_id: T::CurrencyId, who: &T::AccountId) -> bool {
+		if !Accounts::<T>::contains_key(who, currency_id) {
+			return false;
+		}
+		// the dust is removed when mutating the account
+		let (_, maybe_dust) = Self::mutate_account(who, currency_id, |_, _| {});
+		maybe_dust.is_some()
+	}
+
+	/// All currencies held by `who`, together with the account data of each.
+	pub fn account_currencies(who: &T::AccountId) -> Vec<(T::CurrencyId, AccountData<T::Balance>)> {
+		Accounts::<T>::iter_prefix(who).collect()
+	}
+
+	/// Check the accounting invariants of all accounts and currencies:
+	/// - the sum of `free + reserved` of all accounts equals the total
+	///   issuance of each currency
+	/// - named reserves and holds never exceed the reserved balance
+	/// - the frozen balance is the max of all locks and freezes, up to expired
+	///   locks that haven't been pruned yet
+	/// - no account is below the existential deposit unless whitelisted
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut issuance = BTreeMap::<T::CurrencyId, T::Balance>::new();
+		for (who, currency_id, account) in Accounts::<T>::iter() {
+			let total = issuance.entry(currency_id).or_default();
+			*total = total.checked_add(&account.total()).ok_or("total issuance overflow")?;
+
+			let named_reserved = Self::reserves(&who, currency_id)
+				.iter()
+				.map(|reserve| reserve.amount)
+				.chain(Self::holds(&who, currency_id).iter().map(|hold| hold.amount))
+				.try_fold(Zero::zero(), |acc: T::Balance, amount| acc.checked_add(&amount))
+				.ok_or("named reserves overflow")?;
+			ensure!(
+				named_reserved <= account.reserved,
+				"named reserves and holds exceed the reserved balance"
+			);
+
+			// expired locks that haven't been pruned yet may or may not be accounted for
+			let max_frozen = |include_expired: bool| {
+				Self::locks(&who, currency_id)
+					.iter()
+					.filter(|lock| include_expired || !lock.is_expired(&now))
+					.map(|lock| lock.amount)
+					.chain(Self::freezes(&who, currency_id).iter().map(|freeze| freeze.amount))
+					.max()
+					.unwrap_or_else(Zero::zero)
+			};
+			// the locks can't be decoded while they are being migrated
+			ensure!(
+				!Self::locks_migrated() || (max_frozen(false) <= account.frozen && account.frozen <= max_frozen(true)),
+				"frozen balance does not match the max of locks and freezes"
+			);
+
+			ensure!(
+				account.total() >= T::ExistentialDeposits::get(&currency_id) || T::DustRemovalWhitelist::contains(&who),
+				"account is below the existential deposit"
+			);
+		}
+
+		for (currency_id, total_issuance) in TotalIssuance::<T>::iter() {
+			ensure!(
+				issuance.remove(&currency_id).unwrap_or_else(Zero::zero) == total_issuance,
+				"sum of account balances does not match the total issuance"
+			);
+		}
+		ensure!(
+			issuance.values().all(Zero::is_zero),
+			"sum of account balances does not match the total issuance"
+		);
+
+		Ok(())
+	}
+
+	/// The maximum total issuance of `currency_id`.
+	pub(crate) fn max_total_issuance(currency_id: T::CurrencyId) -> T::Balance {
+		T::MaxTotalIssuance::get(&currency_id).unwrap_or_else(Bounded::max_value)
+	}
+
+	pub(crate) fn deposit_consequence(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		amount: T::Balance,
+		account: &AccountData<T::Balance>,
+	) -> DepositConsequence {
+		if amount.is_zero() {
+			return DepositConsequence::Success;
+		}
+
+		match TotalIssuance::<T>::get(currency_id).checked_add(&amount) {
+			Some(new_total_issuance) if new_total_issuance <= Self::max_total_issuance(currency_id) => {}
+			_ => return DepositConsequence::Overflow,
+		}
+
+		let new_total_balance = match account.total().checked_add(&amount) {
+			Some(x) => x,
+			None => return DepositConsequence::Overflow,
+		};
+
+		if new_total_balance < T::ExistentialDeposits::get(&currency_id) {
+			return DepositConsequence::BelowMinimum;
+		}
+
+		if !Accounts::<T>::contains_key(who, currency_id) && Self::ensure_can_create(currency_id, who).is_err() {
+			return DepositConsequence::CannotCreate;
+		}
+
+		if Self::ensure_compliant(currency_id, who).is_err() {
+			return DepositConsequence::Blocked;
+		}
+
+		DepositConsequence::Success
+	}
+
+	pub(crate) fn withdraw_consequence(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		amount: T::Balance,
+		account: &AccountData<T::Balance>,
+	) -> WithdrawConsequence<T::Balance> {
+		if amount.is_zero() {
+			return WithdrawConsequence::Success;
+		}
+
+		if Self::ensure_not_frozen(currency_id, who).is_err() {
+			return WithdrawConsequence::Frozen;
+		}
+
+		if TotalIssuance::<T>::get(currency_id).checked_sub(&amount).is_none() {
+			return WithdrawConsequence::Underflow;
+		}
+
+		let new_total_balance = match account.total().checked_sub(&amount) {
+			Some(x) => x,
+			None => return WithdrawConsequence::BalanceLow,
+		};
+
+		// Provider restriction - total account balance cannot be reduced to zero if it
+		// cannot sustain the loss of a provider reference.
+		let ed = T::ExistentialDeposits::get(&currency_id);
+		let success = if new_total_balance < ed {
+			if Self::can_remove_account(currency_id, who) {
+				WithdrawConsequence::ReducedToZero(new_total_balance)
+			} else {
+				return WithdrawConsequence::WouldDie;
+			}
+		} else {
+			WithdrawConsequence::Success
+		};
+
+		// Enough free funds to have them be reduced.
+		let new_free_balance = match account.free.checked_sub(&amount) {
+			Some(b) => b,
+			None => return WithdrawConsequence::BalanceLow,
+		};
+
+		// Eventual free funds must be no less than the frozen balance.
+		if new_free_balance < Self::frozen_balance(currency_id, who, account) {
+			return WithdrawConsequence::Frozen;
+		}
+
+		success
+	}
+
+	/// Ensure that the account of `who` under `currency_id` can be created. The
+	/// account of a non-sufficient currency takes a consumer reference, so it
+	/// needs `who` to be kept alive by something else.
+	pub(crate) fn ensure_can_create(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		ensure!(
+			T::IsSufficient::contains(&currency_id) || frame_system::Pallet::<T>::can_inc_consumer(who),
+			TokenError::CannotCreate
+		);
+		Ok(())
+	}
+
+	/// Whether the account of `who` under `currency_id` can be removed. Only
+	/// an account which took a provider reference, see `ConsumerAccounts`,
+	/// may be needed by the consumers of `who`.
+	pub(crate) fn can_remove_account(currency_id: T::CurrencyId, who: &T::AccountId) -> bool {
+		ConsumerAccounts::<T>::contains_key(who, currency_id) || frame_system::Pallet::<T>::can_dec_provider(who)
+	}
+
+	/// Ensure that the compliance policy of `currency_id` allows `who` to hold
+	/// and move it.
+	pub(crate) fn ensure_compliant(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		let compliant = match Self::compliance_policies(currency_id) {
+			CompliancePolicy::Open => true,
+			CompliancePolicy::Allowlist => ComplianceMembers::<T>::contains_key(currency_id, who),
+			CompliancePolicy::Denylist => !ComplianceMembers::<T>::contains_key(currency_id, who),
+		};
+		ensure!(compliant, Error::<T>::NotCompliant);
+		Ok(())
+	}
+
+	/// Ensure that neither `currency_id` nor `who` under `currency_id` has been
+	/// frozen by `T::FreezeOrigin`.
+	pub(crate) fn ensure_not_frozen(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		ensure!(
+			!FrozenCurrencies::<T>::contains_key(currency_id),
+			Error::<T>::CurrencyFrozen
+		);
+		ensure!(
+			!FrozenAccounts::<T>::contains_key(who, currency_id),
+			Error::<T>::AccountFrozen
+		);
+		Ok(())
+	}
+
+	// Ensure that an account can withdraw from their free balance given any
+	// existing withdrawal restrictions like locks and vesting balance.
+	// Is a no-op if amount to be withdrawn is zero.
+	pub(crate) fn ensure_can_withdraw(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Self::ensure_can_withdraw_with_reasons(currency_id, who, amount, WithdrawReasons::all())
+	}
+
+	// Ensure that an account can withdraw from their free balance for any of
+	// `reasons`, given the locks which prevent withdrawals for these reasons
+	// and the freezes.
+	// Is a no-op if amount to be withdrawn is zero.
+	pub(crate) fn ensure_can_withdraw_with_reasons(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let account = Self::accounts(who, currency_id);
+		let new_balance = account.free.checked_sub(&amount).ok_or(Error::<T>::BalanceTooLow)?;
+		ensure!(
+			new_balance >= Self::frozen_balance_with_reasons(currency_id, who, &account, reasons),
+			Error::<T>::LiquidityRestrictions
+		);
+		Ok(())
+	}
+
+	pub(crate) fn try_mutate_account<R, E>(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> sp_std::result::Result<R, E>,
+	) -> sp_std::result::Result<(R, Option<T::Balance>), E> {
+		// lazily release the balance frozen by expired locks
+		if !Self::accounts(who, currency_id).frozen.is_zero() {
+			Self::prune_expired_locks(currency_id, who);
+		}
+
+		Accounts::<T>::try_mutate_exists(who, currency_id, |maybe_account| {
+			let existed = maybe_account.is_some();
+			let mut account = maybe_account.take().unwrap_or_default();
+			let old_total = account.total();
+			f(&mut account, existed).map(move |result| {
+				let maybe_endowed = if !existed { Some(account.free) } else { None };
+				let mut maybe_dust: Option<T::Balance> = None;
+				let total = account.total();
+				*maybe_account = if total < T::ExistentialDeposits::get(&currency_id) {
+					// if ED is not zero, but account total is zero, account will be reaped
+					if total.is_zero() {
+						None
+					} else {
+						if !T::DustRemovalWhitelist::contains(who) {
+							maybe_dust = Some(total);
+						}
+						Some(account)
+					}
+				} else {
+					// Note: if ED is zero, account will never be reaped
+					Some(account)
+				};
+
+				(maybe_endowed, existed, maybe_account.is_some(), maybe_dust, old_total, total, result)
+			})
+		})
+		.map(|(maybe_endowed, existed, exists, maybe_dust, old_total, new_total, result)| {
+			if T::CheckpointCurrencies::contains(&currency_id) {
+				if existed && !exists {
+					// the history of an account is removed with it
+					BalanceCheckpoints::<T>::remove(who, currency_id);
+				} else if old_total != new_total {
+					BalanceCheckpoints::<T>::mutate(who, currency_id, |checkpoints| {
+						Self::record_checkpoint(checkpoints, currency_id, existed.then_some(old_total), new_total)
+					});
+				}
+			}
+
+			if existed && !exists {
+				// release the reference the account took when it was created
+				if ConsumerAccounts::<T>::take(who, currency_id).is_some() {
+					frame_system::Pallet::<T>::dec_consumers(who);
+				} else {
+					// If existed before, decrease account provider.
+					// Ignore the result, because if it failed then there are remaining consumers,
+					// and the account storage in frame_system shouldn't be reaped.
+					let _ = frame_system::Pallet::<T>::dec_providers(who);
+				}
+				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnKilledTokenAccount::happened(&(who.clone(), currency_id));
+			} else if !existed && exists {
+				if T::IsSufficient::contains(&currency_id) {
+					// if new, increase account provider
+					frame_system::Pallet::<T>::inc_providers(who);
+				} else {
+					if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
+						// The fallible mutations ensure the account can be created, see
+						// `ensure_can_create`. This is unexpected but should be safe.
+						log::warn!(
+							"Warning: Attempt to introduce token account consumer reference, yet no providers."
+						);
+					}
+					ConsumerAccounts::<T>::insert(who, currency_id, ());
+				}
+				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnNewTokenAccount::happened(&(who.clone(), currency_id));
+			}
+
+			if let Some(endowed) = maybe_endowed {
+				Self::deposit_event(Event::Endowed {
+					currency_id,
+					who: who.clone(),
+					amount: endowed,
+				});
+			}
+
+			if let Some(dust_amount) = maybe_dust {
+				// `OnDust` maybe get/set storage `Accounts` of `who`, trigger handler here
+				// to avoid some unexpected errors.
+				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnDust::on_dust(who, currency_id, dust_amount);
+
+				Self::deposit_event(Event::DustLost {
+					currency_id,
+					who: who.clone(),
+					amount: dust_amount,
+				});
+			}
+
+			(result, maybe_dust)
+		})
+	}
+
+	/// Mutate the total issuance of `currency_id`, and record the checkpoint
+	/// if needed.
+	pub(crate) fn try_mutate_total_issuance<R, E>(
+		currency_id: T::CurrencyId,
+		f: impl FnOnce(&mut T::Balance) -> sp_std::result::Result<R, E>,
+	) -> sp_std::result::Result<R, E> {
+		TotalIssuance::<T>::try_mutate(currency_id, |total_issuance| {
+			let old_total_issuance = *total_issuance;
+			let result = f(total_issuance)?;
+			if old_total_issuance != *total_issuance && T::CheckpointCurrencies::contains(&currency_id) {
+				TotalIssuanceCheckpoints::<T>::mutate(currency_id, |checkpoints| {
+					Self::record_checkpoint(checkpoints, currency_id, Some(old_total_issuance), *total_issuance)
+				});
+			}
+			Ok(result)
+		})
+	}
+
+	pub(crate) fn mutate_total_issuance<R>(currency_id: T::CurrencyId, f: impl FnOnce(&mut T::Balance) -> R) -> R {
+		Self::try_mutate_total_issuance(currency_id, |total_issuance| -> Result<R, Infallible> {
+			Ok(f(total_issuance))
+		})
+		.expect("Error is infallible; qed")
+	}
+
+	/// Record `new` as the balance at the end of the current block. If there
+	/// is no checkpoint yet, `old` is recorded as the balance since the
+	/// history of `currency_id` started, unless there was no balance before.
+	fn record_checkpoint(
+		checkpoints: &mut BoundedVec<Checkpoint<BlockNumberFor<T>, T::Balance>, T::MaxCheckpoints>,
+		currency_id: T::CurrencyId,
+		old: Option<T::Balance>,
+		new: T::Balance,
+	) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = CheckpointStarts::<T>::get(currency_id).unwrap_or_else(|| {
+			CheckpointStarts::<T>::insert(currency_id, now);
+			now
+		});
+		if let (true, Some(old)) = (checkpoints.is_empty(), old) {
+			let _ = checkpoints.try_push(Checkpoint {
+				block_number: start,
+				balance: old,
+			});
+		}
+
+		match checkpoints.last_mut() {
+			Some(last) if last.block_number == now => last.balance = new,
+			_ => {
+				if checkpoints.len() >= T::MaxCheckpoints::get() as usize && !checkpoints.is_empty() {
+					checkpoints.remove(0);
+				}
+				let _ = checkpoints.try_push(Checkpoint {
+					block_number: now,
+					balance: new,
+				});
+			}
+		}
+	}
+
+	/// The balance of the last checkpoint at or before `block_number`.
+	fn checkpoint_at(
+		checkpoints: &[Checkpoint<BlockNumberFor<T>, T::Balance>],
+		block_number: BlockNumberFor<T>,
+	) -> Option<T::Balance> {
+		let index = checkpoints.partition_point(|checkpoint| checkpoint.block_number <= block_number);
+		index.checked_sub(1).map(|index| checkpoints[index].balance)
+	}
+
+	/// The total balance of `who` at the end of `block_number`.
+	///
+	/// Returns `None` if `currency_id` is not in `CheckpointCurrencies`,
+	/// `block_number` is before the history of `currency_id` started, the
+	/// checkpoints of `block_number` were removed, or `who` had no account at
+	/// `block_number` and since then.
+	pub fn balance_at(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		block_number: BlockNumberFor<T>,
+	) -> Option<T::Balance> {
+		if !T::CheckpointCurrencies::contains(&currency_id) || block_number < Self::checkpoint_starts(currency_id)? {
+			return None;
+		}
+
+		let checkpoints = Self::balance_checkpoints(who, currency_id);
+		if checkpoints.is_empty() {
+			// not changed since the checkpoints are recorded, the history of removed
+			// accounts is removed too
+			return Accounts::<T>::contains_key(who, currency_id).then(|| Self::accounts(who, currency_id).total());
+		}
+		Self::checkpoint_at(&checkpoints, block_number)
+	}
+
+	/// The total issuance of `currency_id` at the end of `block_number`.
+	///
+	/// Returns `None` if `currency_id` is not in `CheckpointCurrencies`,
+	/// `block_number` is before the history of `currency_id` started, or the
+	/// checkpoints of `block_number` were removed.
+	pub fn total_issuance_at(currency_id: T::CurrencyId, block_number: BlockNumberFor<T>) -> Option<T::Balance> {
+		if !T::CheckpointCurrencies::contains(&currency_id) || block_number < Self::checkpoint_starts(currency_id)? {
+			return None;
+		}
+
+		let checkpoints = Self::total_issuance_checkpoints(currency_id);
+		if checkpoints.is_empty() {
+			// not changed since the checkpoints are recorded
+			return Some(Self::total_issuance(currency_id));
+		}
+		Self::checkpoint_at(&checkpoints, block_number)
+	}
+
+	pub(crate) fn mutate_account<R>(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> R,
+	) -> (R, Option<T::Balance>) {
+		Self::try_mutate_account(who, currency_id, |account, existed| -> Result<R, Infallible> {
+			Ok(f(account, existed))
+		})
+		.expect("Error is infallible; qed")
+	}
+
+	/// Set free balance of `who` to a new value.
+	///
+	/// Note: this will not maintain total issuance, and the caller is expected
+	/// to do it. If it will cause the account to be removed dust, shouldn't use
+	/// it, because maybe the account that should be reaped to remain due to
+	/// failed transfer/withdraw dust.
+	pub(crate) fn set_free_balance(currency_id: T::CurrencyId, who: &T::AccountId, amount: T::Balance) {
+		Self::mutate_account(who, currency_id, |account, _| {
+			account.free = amount;
+
+			Self::deposit_event(Event::BalanceSet {
+				currency_id,
+				who: who.clone(),
+				free: account.free,
+				reserved: account.reserved,
+			});
+		});
+	}
+
+	/// Set reserved balance of `who` to a new value.
+	///
+	/// Note: this will not maintain total issuance, and the caller is expected
+	/// to do it. If it will cause the account to be removed dust, shouldn't use
+	/// it, because maybe the account that should be reaped to remain due to
+	/// failed transfer/withdraw dust.
+	pub(crate) fn set_reserved_balance(currency_id: T::CurrencyId, who: &T::AccountId, amount: T::Balance) {
+		Self::mutate_account(who, currency_id, |account, _| {
+			account.reserved = amount;
+
+			Self::deposit_event(Event::BalanceSet {
+				currency_id,
+				who: who.clone(),
+				free: account.free,
+				reserved: account.reserved,
+			});
+		});
+	}
+
+	/// Update the account entry for `who` under `currency_id`, given the
+	/// locks.
+	pub(crate) fn update_locks(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		locks: &[BalanceLock<T::Balance, BlockNumberFor<T>>],
+	) -> DispatchResult {
+		ensure!(Self::locks_migrated(), Error::<T>::LocksMigrating);
+
+		// update locks before the account data, so that pruning expired locks
+		// on the account mutation sees the new locks
+		let existed = Locks::<T>::contains_key(who, currency_id);
+		if locks.is_empty() {
+			Locks::<T>::remove(who, currency_id);
+		} else {
+			let bounded_locks: BoundedVec<BalanceLock<T::Balance, BlockNumberFor<T>>, T::MaxLocks> =
+				locks.to_vec().try_into().map_err(|_| Error::<T>::MaxLocksExceeded)?;
+			Locks::<T>::insert(who, currency_id, bounded_locks);
+		}
+
+		// update account data
+		Self::update_frozen(currency_id, who, locks, &Self::freezes(who, currency_id));
+
+		if locks.is_empty() {
+			if existed {
+				// decrease account ref count when destruct lock
+				frame_system::Pallet::<T>::dec_consumers(who);
+			}
+		} else if !existed {
+			// increase account ref count when initialize lock
+			if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
+				// No providers for the locks. This is impossible under normal circumstances
+				// since the funds that are under the lock will themselves be stored in the
+				// account and therefore will need a reference.
+				log::warn!(
+					"Warning: Attempt to introduce lock consumer reference, yet no providers. \
+					This is unexpected but should be safe."
+				);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Set the lock `lock_id` on `who` under `currency_id`, replacing any
+	/// existing lock with the same identifier. Is a no-op if `amount` is zero,
+	/// and removes the lock if `reasons` are empty.
+	pub(crate) fn do_set_lock(
+		lock_id: LockIdentifier,
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		expires_at: Option<BlockNumberFor<T>>,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		if reasons.is_empty() {
+			return <Self as MultiLockableCurrency<_>>::remove_lock(lock_id, currency_id, who);
+		}
+		let mut new_lock = Some(BalanceLock {
+			id: lock_id,
+			amount,
+			expires_at,
+			reasons: reasons.into(),
+		});
+		let mut locks = Self::locks(who, currency_id)
+			.into_iter()
+			.filter_map(|lock| {
+				if lock.id == lock_id {
+					new_lock.take()
+				} else {
+					Some(lock)
+				}
+			})
+			.collect::<Vec<_>>();
+		if let Some(lock) = new_lock {
+			locks.push(lock)
+		}
+		Self::update_locks(currency_id, who, &locks[..])?;
+
+		Self::deposit_event(Event::LockSet {
+			lock_id,
+			currency_id,
+			who: who.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Extend the lock `lock_id` on `who` under `currency_id` to the larger
+	/// amount, the later expiry, `None` meaning the lock never expires, and
+	/// the union of the reasons. An expired lock is replaced. Is a no-op if
+	/// `amount` or `reasons` are zero.
+	pub(crate) fn do_extend_lock(
+		lock_id: LockIdentifier,
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		expires_at: Option<BlockNumberFor<T>>,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if amount.is_zero() || reasons.is_empty() {
+			return Ok(());
+		}
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut new_lock = Some(BalanceLock {
+			id: lock_id,
+			amount,
+			expires_at,
+			reasons: reasons.into(),
+		});
+		let mut locks = Self::locks(who, currency_id)
+			.into_iter()
+			.filter_map(|lock| {
+				if lock.id == lock_id {
+					new_lock.take().map(|nl| {
+						let lock = if lock.is_expired(&now) {
+							nl
+						} else {
+							BalanceLock {
+								id: lock.id,
+								amount: lock.amount.max(nl.amount),
+								expires_at: lock.expires_at.zip(nl.expires_at).map(|(a, b)| a.max(b)),
+								reasons: (WithdrawReasons::from(lock.reasons) | reasons).into(),
+							}
+						};
+						Self::deposit_event(Event::LockSet {
+							lock_id,
+							currency_id,
+							who: who.clone(),
+							amount: lock.amount,
+						});
+						lock
+					})
+				} else {
+					Some(lock)
+				}
+			})
+			.collect::<Vec<_>>();
+		if let Some(lock) = new_lock {
+			Self::deposit_event(Event::LockSet {
+				lock_id,
+				currency_id,
+				who: who.clone(),
+				amount: lock.amount,
+			});
+			locks.push(lock)
+		}
+		Self::update_locks(currency_id, who, &locks[..])
+	}
 
-		// Provider restriction - total account balance cannot be reduced to zero if it
-		// cannot sustain the loss of a provider reference.
-		// NOTE: This assumes that the pallet is a provider (which is true). Is this
-		// ever changes, then this will need to adapt accordingly.
-		let ed = T::ExistentialDeposits::get(&currency_id);
-		let success = if new_total_balance < ed {
-			if frame_system::Pallet::<T>::can_dec_provider(who) {
-				WithdrawConsequence::ReducedToZero(new_total_balance)
-			} else {
-				return WithdrawConsequence::WouldDie;
-			}
+	/// Remove the locks of `who` under `currency_id` that have expired, and
+	/// release the balance they froze.
+	pub(crate) fn prune_expired_locks(currency_id: T::CurrencyId, who: &T::AccountId) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let (expired, locks): (Vec<_>, Vec<_>) = Self::locks(who, currency_id)
+			.into_iter()
+			.partition(|lock| lock.is_expired(&now));
+		if expired.is_empty() || !Self::locks_migrated() {
+			return;
+		}
+
+		// removing locks can't exceed `MaxLocks`
+		let _ = Self::update_locks(currency_id, who, &locks[..]);
+		for lock in expired {
+			Self::deposit_event(Event::LockRemoved {
+				lock_id: lock.id,
+				currency_id,
+				who: who.clone(),
+			});
+		}
+	}
+
+	/// The weight of pruning the expired locks of `accounts` accounts when they
+	/// are mutated, which the benchmarked weights of the calls don't include.
+	pub(crate) fn prune_expired_locks_weight(accounts: u32) -> Weight {
+		// read the locks, freezes and account, in the worst case write the locks,
+		// the account and the system account
+		T::DbWeight::get().reads_writes(3, 3).saturating_mul(accounts.into())
+	}
+
+	/// Whether `Locks` has the layout of the current storage version. Until
+	/// `MigrateToV1` and `MigrateToV2` complete, the locks can't be decoded,
+	/// so neither locks nor freezes can be changed and the frozen balance of
+	/// the accounts is kept as is.
+	pub(crate) fn locks_migrated() -> bool {
+		Self::on_chain_storage_version() >= 2
+	}
+
+	/// Prune expired locks, resuming where the previous call stopped, until
+	/// `weight_limit` is used up.
+	pub(crate) fn prune_expired_locks_on_idle(weight_limit: Weight) -> Weight {
+		// read and write the cursor
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		if weight.any_gt(weight_limit) {
+			return Weight::zero();
+		}
+
+		// read the locks, freezes and account, in the worst case write the locks,
+		// the account and the system account
+		let weight_per_entry = T::DbWeight::get().reads_writes(3, 3);
+		let now = frame_system::Pallet::<T>::block_number();
+		let cursor = LockCleanupCursor::<T>::take();
+		let mut iter = if cursor.is_empty() {
+			Locks::<T>::iter()
 		} else {
-			WithdrawConsequence::Success
+			Locks::<T>::iter_from(cursor)
 		};
+		loop {
+			if weight.saturating_add(weight_per_entry).any_gt(weight_limit) {
+				LockCleanupCursor::<T>::put(iter.last_raw_key().to_vec());
+				break;
+			}
+			match iter.next() {
+				Some((who, currency_id, locks)) => {
+					weight.saturating_accrue(weight_per_entry);
+					if locks.iter().any(|lock| lock.is_expired(&now)) {
+						Self::prune_expired_locks(currency_id, &who);
+					}
+				}
+				// reached the end, start over on the next call
+				None => break,
+			}
+		}
 
-		// Enough free funds to have them be reduced.
-		let new_free_balance = match account.free.checked_sub(&amount) {
-			Some(b) => b,
-			None => return WithdrawConsequence::BalanceLow,
-		};
+		weight
+	}
 
-		// Eventual free funds must be no less than the frozen balance.
-		if new_free_balance < account.frozen() {
-			return WithdrawConsequence::Frozen;
+	/// The balance of `who` under `currency_id` that can't be withdrawn,
+	/// ignoring locks that have expired but haven't been pruned yet.
+	pub(crate) fn frozen_balance(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		account: &AccountData<T::Balance>,
+	) -> T::Balance {
+		Self::frozen_balance_with_reasons(currency_id, who, account, WithdrawReasons::all())
+	}
+
+	/// The balance of `who` under `currency_id` that can't be withdrawn for
+	/// any of `reasons`, ignoring locks that have expired but haven't been
+	/// pruned yet. Freezes prevent withdrawals for all reasons.
+	pub(crate) fn frozen_balance_with_reasons(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		account: &AccountData<T::Balance>,
+		reasons: WithdrawReasons,
+	) -> T::Balance {
+		if account.frozen.is_zero() {
+			return Zero::zero();
 		}
 
-		success
+		let now = frame_system::Pallet::<T>::block_number();
+		let locks = Self::locks(who, currency_id);
+		if !locks
+			.iter()
+			.any(|lock| lock.is_expired(&now) || !lock.reasons.intersects(reasons))
+		{
+			return account.frozen();
+		}
+		if !Self::locks_migrated() {
+			return account.frozen();
+		}
+		locks
+			.iter()
+			.filter(|lock| !lock.is_expired(&now) && lock.reasons.intersects(reasons))
+			.map(|lock| lock.amount)
+			.chain(Self::freezes(who, currency_id).iter().map(|freeze| freeze.amount))
+			.max()
+			.unwrap_or_else(Zero::zero)
 	}
 
-	// Ensure that an account can withdraw from their free balance given any
-	// existing withdrawal restrictions like locks and vesting balance.
-	// Is a no-op if amount to be withdrawn is zero.
-	pub(crate) fn ensure_can_withdraw(
+	/// Update the account entry for `who` under `currency_id`, given the
+	/// freezes.
+	pub(crate) fn update_freezes(
 		currency_id: T::CurrencyId,
 		who: &T::AccountId,
-		amount: T::Balance,
+		freezes: &[IdAmount<T::FreezeIdentifier, T::Balance>],
 	) -> DispatchResult {
-		if amount.is_zero() {
-			return Ok(());
+		ensure!(Self::locks_migrated(), Error::<T>::LocksMigrating);
+
+		let bounded_freezes: BoundedVec<IdAmount<T::FreezeIdentifier, T::Balance>, T::MaxFreezes> =
+			freezes.to_vec().try_into().map_err(|_| Error::<T>::TooManyFreezes)?;
+
+		// update account data
+		Self::update_frozen(currency_id, who, &Self::locks(who, currency_id), freezes);
+
+		// update freezes
+		let existed = Freezes::<T>::contains_key(who, currency_id);
+		if freezes.is_empty() {
+			Freezes::<T>::remove(who, currency_id);
+			if existed {
+				// decrease account ref count when destruct freeze
+				frame_system::Pallet::<T>::dec_consumers(who);
+			}
+		} else {
+			Freezes::<T>::insert(who, currency_id, bounded_freezes);
+			if !existed {
+				// increase account ref count when initialize freeze
+				if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
+					// No providers for the freezes. This is impossible under normal
+					// circumstances since the funds that are under the freeze will themselves be
+					// stored in the account and therefore will need a reference.
+					log::warn!(
+						"Warning: Attempt to introduce freeze consumer reference, yet no providers. \
+						This is unexpected but should be safe."
+					);
+				}
+			}
 		}
 
-		let new_balance = Self::free_balance(currency_id, who)
-			.checked_sub(&amount)
-			.ok_or(Error::<T>::BalanceTooLow)?;
-		ensure!(
-			new_balance >= Self::accounts(who, currency_id).frozen(),
-			Error::<T>::LiquidityRestrictions
-		);
 		Ok(())
 	}
 
-	pub(crate) fn try_mutate_account<R, E>(
-		who: &T::AccountId,
+	/// Set the frozen balance of `who` under `currency_id` to the largest of
+	/// the given locks and freezes. Expired locks are ignored.
+	fn update_frozen(
 		currency_id: T::CurrencyId,
-		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> sp_std::result::Result<R, E>,
-	) -> sp_std::result::Result<(R, Option<T::Balance>), E> {
-		Accounts::<T>::try_mutate_exists(who, currency_id, |maybe_account| {
-			let existed = maybe_account.is_some();
-			let mut account = maybe_account.take().unwrap_or_default();
-			f(&mut account, existed).map(move |result| {
-				let maybe_endowed = if !existed { Some(account.free) } else { None };
-				let mut maybe_dust: Option<T::Balance> = None;
-				let total = account.total();
-				*maybe_account = if total < T::ExistentialDeposits::get(&currency_id) {
-					// if ED is not zero, but account total is zero, account will be reaped
-					if total.is_zero() {
-						None
-					} else {
-						if !T::DustRemovalWhitelist::contains(who) {
-							maybe_dust = Some(total);
-						}
-						Some(account)
-					}
-				} else {
-					// Note: if ED is zero, account will never be reaped
-					Some(account)
-				};
+		who: &T::AccountId,
+		locks: &[BalanceLock<T::Balance, BlockNumberFor<T>>],
+		freezes: &[IdAmount<T::FreezeIdentifier, T::Balance>],
+	) {
+		// track frozen delta
+		let mut total_frozen_prev = Zero::zero();
+		let mut total_frozen_after = Zero::zero();
 
-				(maybe_endowed, existed, maybe_account.is_some(), maybe_dust, result)
-			})
-		})
-		.map(|(maybe_endowed, existed, exists, maybe_dust, result)| {
-			if existed && !exists {
-				// If existed before, decrease account provider.
-				// Ignore the result, because if it failed then there are remaining consumers,
-				// and the account storage in frame_system shouldn't be reaped.
-				let _ = frame_system::Pallet::<T>::dec_providers(who);
-				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnKilledTokenAccount::happened(&(who.clone(), currency_id));
-			} else if !existed && exists {
-				// if new, increase account provider
-				frame_system::Pallet::<T>::inc_providers(who);
-				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnNewTokenAccount::happened(&(who.clone(), currency_id));
+		let now = frame_system::Pallet::<T>::block_number();
+		Self::mutate_account(who, currency_id, |account, _| {
+			total_frozen_prev = account.frozen;
+			account.frozen = Zero::zero();
+			for lock in locks.iter().filter(|lock| !lock.is_expired(&now)) {
+				account.frozen = account.frozen.max(lock.amount);
 			}
-
-			if let Some(endowed) = maybe_endowed {
-				Self::deposit_event(Event::Endowed {
-					currency_id,
-					who: who.clone(),
-					amount: endowed,
-				});
+			for freeze in freezes.iter() {
+				account.frozen = account.frozen.max(freeze.amount);
 			}
+			total_frozen_after = account.frozen;
+		});
 
-			if let Some(dust_amount) = maybe_dust {
-				// `OnDust` maybe get/set storage `Accounts` of `who`, trigger handler here
-				// to avoid some unexpected errors.
-				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnDust::on_dust(who, currency_id, dust_amount);
-
-				Self::deposit_event(Event::DustLost {
-					currency_id,
-					who: who.clone(),
-					amount: dust_amount,
-				});
-			}
+		if total_frozen_prev < total_frozen_after {
+			let amount = total_frozen_after.saturating_sub(total_frozen_prev);
+			Self::deposit_event(Event::Locked {
+				currency_id,
+				who: who.clone(),
+				amount,
+			});
+		} else if total_frozen_prev > total_frozen_after {
+			let amount = total_frozen_prev.saturating_sub(total_frozen_after);
+			Self::deposit_event(Event::Unlocked {
+				currency_id,
+				who: who.clone(),
+				amount,
+			});
+		}
+	}
 
-			(result, maybe_dust)
-		})
+	/// The reserved balance of `who` under `currency_id` which can be moved,
+	/// excluding the named and held balance unless `include_named`.
+	pub(crate) fn movable_reserved_balance(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		include_named: bool,
+	) -> T::Balance {
+		let reserved = Self::accounts(who, currency_id).reserved;
+		if include_named {
+			return reserved;
+		}
+		let named_reserved = Self::reserves(who, currency_id)
+			.iter()
+			.map(|reserve| reserve.amount)
+			.chain(Self::holds(who, currency_id).iter().map(|hold| hold.amount))
+			.fold(Zero::zero(), |acc: T::Balance, amount| acc.saturating_add(amount));
+		reserved.saturating_sub(named_reserved)
 	}
 
-	pub(crate) fn mutate_account<R>(
+	/// Slash from reserved balance, returning any amount that was unable to
+	/// be slashed. Named and held balance is only slashed if `include_named`.
+	///
+	/// Is a no-op if the value to be slashed is zero.
+	pub(crate) fn do_slash_reserved(
+		currency_id: T::CurrencyId,
 		who: &T::AccountId,
+		value: T::Balance,
+		include_named: bool,
+	) -> T::Balance {
+		if value.is_zero() {
+			return value;
+		}
+
+		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnSlash::on_slash(
+			currency_id,
+			who,
+			value,
+		);
+		let reserved_balance = Self::accounts(who, currency_id).reserved;
+		let actual = Self::movable_reserved_balance(currency_id, who, include_named).min(value);
+		Self::mutate_account(who, currency_id, |account, _| {
+			// ensured reserved_balance >= actual but just to be defensive here.
+			account.reserved = reserved_balance.defensive_saturating_sub(actual);
+		});
+		Self::mutate_total_issuance(currency_id, |v| *v = v.defensive_saturating_sub(actual));
+
+		Self::deposit_event(Event::Slashed {
+			currency_id,
+			who: who.clone(),
+			free_amount: Zero::zero(),
+			reserved_amount: actual,
+		});
+		value.defensive_saturating_sub(actual)
+	}
+
+	/// Unreserve some funds, returning any amount that was unable to be
+	/// unreserved. Named and held funds are only unreserved if
+	/// `include_named`.
+	///
+	/// Is a no-op if the value to be unreserved is zero.
+	pub(crate) fn do_unreserve(
 		currency_id: T::CurrencyId,
-		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> R,
-	) -> (R, Option<T::Balance>) {
-		Self::try_mutate_account(who, currency_id, |account, existed| -> Result<R, Infallible> {
-			Ok(f(account, existed))
-		})
-		.expect("Error is infallible; qed")
+		who: &T::AccountId,
+		value: T::Balance,
+		include_named: bool,
+	) -> T::Balance {
+		if value.is_zero() {
+			return value;
+		}
+
+		let movable = Self::movable_reserved_balance(currency_id, who, include_named);
+		let (remaining, _) = Self::mutate_account(who, currency_id, |account, _| {
+			let actual = movable.min(value);
+			account.reserved = account.reserved.defensive_saturating_sub(actual);
+			account.free = account.free.defensive_saturating_add(actual);
+
+			Self::deposit_event(Event::Unreserved {
+				currency_id,
+				who: who.clone(),
+				amount: actual,
+			});
+			value.defensive_saturating_sub(actual)
+		});
+
+		remaining
 	}
 
-	/// Set free balance of `who` to a new value.
+	/// Move the reserved balance of one account into the balance of
+	/// another, according to `status`. Named and held balance is only moved
+	/// if `include_named`.
 	///
-	/// Note: this will not maintain total issuance, and the caller is expected
-	/// to do it. If it will cause the account to be removed dust, shouldn't use
-	/// it, because maybe the account that should be reaped to remain due to
-	/// failed transfer/withdraw dust.
-	pub(crate) fn set_free_balance(currency_id: T::CurrencyId, who: &T::AccountId, amount: T::Balance) {
-		Self::mutate_account(who, currency_id, |account, _| {
-			account.free = amount;
+	/// Is a no-op if:
+	/// - the value to be moved is zero; or
+	/// - the `slashed` id equal to `beneficiary` and the `status` is
+	///   `Reserved`.
+	pub(crate) fn do_repatriate_reserved(
+		currency_id: T::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: T::Balance,
+		status: BalanceStatus,
+		include_named: bool,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		if value.is_zero() {
+			return Ok(value);
+		}
+
+		if slashed == beneficiary {
+			return match status {
+				BalanceStatus::Free => Ok(Self::do_unreserve(currency_id, slashed, value, include_named)),
+				BalanceStatus::Reserved => Ok(value.saturating_sub(Self::accounts(slashed, currency_id).reserved)),
+			};
+		}
+
+		Self::ensure_not_frozen(currency_id, slashed)?;
+		Self::ensure_compliant(currency_id, slashed)?;
+		Self::ensure_compliant(currency_id, beneficiary)?;
+		if !Accounts::<T>::contains_key(beneficiary, currency_id) {
+			Self::ensure_can_create(currency_id, beneficiary)?;
+		}
+
+		let from_account = Self::accounts(slashed, currency_id);
+		let to_account = Self::accounts(beneficiary, currency_id);
+		let actual = Self::movable_reserved_balance(currency_id, slashed, include_named).min(value);
+		match status {
+			BalanceStatus::Free => {
+				Self::set_free_balance(
+					currency_id,
+					beneficiary,
+					to_account.free.defensive_saturating_add(actual),
+				);
+			}
+			BalanceStatus::Reserved => {
+				Self::set_reserved_balance(
+					currency_id,
+					beneficiary,
+					to_account.reserved.defensive_saturating_add(actual),
+				);
+			}
+		}
+		Self::set_reserved_balance(
+			currency_id,
+			slashed,
+			from_account.reserved.defensive_saturating_sub(actual),
+		);
 
-			Self::deposit_event(Event::BalanceSet {
-				currency_id,
-				who: who.clone(),
-				free: account.free,
-				reserved: account.reserved,
-			});
+		Self::deposit_event(Event::<T>::ReserveRepatriated {
+			currency_id,
+			from: slashed.clone(),
+			to: beneficiary.clone(),
+			amount: actual,
+			status,
 		});
+		Ok(value.defensive_saturating_sub(actual))
 	}
 
-	/// Set reserved balance of `who` to a new value.
+	/// Set the amount held for `reason` on `who` under `currency_id`, removing
+	/// the hold if the amount is zero.
 	///
-	/// Note: this will not maintain total issuance, and the caller is expected
-	/// to do it. If it will cause the account to be removed dust, shouldn't use
-	/// it, because maybe the account that should be reaped to remain due to
-	/// failed transfer/withdraw dust.
-	pub(crate) fn set_reserved_balance(currency_id: T::CurrencyId, who: &T::AccountId, amount: T::Balance) {
-		Self::mutate_account(who, currency_id, |account, _| {
-			account.reserved = amount;
-
-			Self::deposit_event(Event::BalanceSet {
-				currency_id,
-				who: who.clone(),
-				free: account.free,
-				reserved: account.reserved,
-			});
-		});
-	}
-
-	/// Update the account entry for `who` under `currency_id`, given the
-	/// locks.
-	pub(crate) fn update_locks(
+	/// Note: this only updates `Holds`, the caller is expected to move the
+	/// funds between free and reserved balance.
+	pub(crate) fn set_hold(
 		currency_id: T::CurrencyId,
 		who: &T::AccountId,
-		locks: &[BalanceLock<T::Balance>],
+		reason: &T::RuntimeHoldReason,
+		amount: T::Balance,
 	) -> DispatchResult {
-		// track lock delta
-		let mut total_frozen_prev = Zero::zero();
-		let mut total_frozen_after = Zero::zero();
-
-		// update account data
-		Self::mutate_account(who, currency_id, |account, _| {
-			total_frozen_prev = account.frozen;
-			account.frozen = Zero::zero();
-			for lock in locks.iter() {
-				account.frozen = account.frozen.max(lock.amount);
-			}
-			total_frozen_after = account.frozen;
-		});
-
-		// update locks
-		let existed = Locks::<T>::contains_key(who, currency_id);
-		if locks.is_empty() {
-			Locks::<T>::remove(who, currency_id);
-			if existed {
-				// decrease account ref count when destruct lock
-				frame_system::Pallet::<T>::dec_consumers(who);
-			}
-		} else {
-			let bounded_locks: BoundedVec<BalanceLock<T::Balance>, T::MaxLocks> =
-				locks.to_vec().try_into().map_err(|_| Error::<T>::MaxLocksExceeded)?;
-			Locks::<T>::insert(who, currency_id, bounded_locks);
-			if !existed {
-				// increase account ref count when initialize lock
-				if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
-					// No providers for the locks. This is impossible under normal circumstances
-					// since the funds that are under the lock will themselves be stored in the
-					// account and therefore will need a reference.
-					log::warn!(
-						"Warning: Attempt to introduce lock consumer reference, yet no providers. \
-						This is unexpected but should be safe."
-					);
+		Holds::<T>::try_mutate_exists(who, currency_id, |maybe_holds| -> DispatchResult {
+			let mut holds = maybe_holds.take().unwrap_or_default();
+			match holds.binary_search_by_key(reason, |data| data.id) {
+				Ok(index) => {
+					if amount.is_zero() {
+						holds.remove(index);
+					} else {
+						holds[index].amount = amount;
+					}
 				}
-			}
-		}
-
-		if total_frozen_prev < total_frozen_after {
-			let amount = total_frozen_after.saturating_sub(total_frozen_prev);
-			Self::deposit_event(Event::Locked {
-				currency_id,
-				who: who.clone(),
-				amount,
-			});
-		} else if total_frozen_prev > total_frozen_after {
-			let amount = total_frozen_prev.saturating_sub(total_frozen_after);
-			Self::deposit_event(Event::Unlocked {
-				currency_id,
-				who: who.clone(),
-				amount,
-			});
-		}
-
-		Ok(())
+				Err(index) => {
+					if !amount.is_zero() {
+						holds
+							.try_insert(index, IdAmount { id: *reason, amount })
+							.map_err(|_| Error::<T>::TooManyHolds)?;
+					}
+				}
+			};
+			*maybe_holds = if holds.is_empty() { None } else { Some(holds) };
+			Ok(())
+		})
 	}
 
 	/// Transfer some free balance from `from` to `to`. Ensure from_account
@@ -951,13 +2766,19 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
+		Self::ensure_not_frozen(currency_id, from)?;
+		Self::ensure_compliant(currency_id, from)?;
+		Self::ensure_compliant(currency_id, to)?;
 		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PreTransfer::on_transfer(
 			currency_id,
 			from,
 			to,
 			amount,
 		)?;
-		Self::try_mutate_account(to, currency_id, |to_account, _existed| -> DispatchResult {
+		Self::try_mutate_account(to, currency_id, |to_account, existed| -> DispatchResult {
+			if !existed {
+				Self::ensure_can_create(currency_id, to)?;
+			}
 			Self::try_mutate_account(from, currency_id, |from_account, _existed| -> DispatchResult {
 				from_account.free = from_account
 					.free
@@ -974,10 +2795,10 @@ impl<T: Config> Pallet<T> {
 					Error::<T>::ExistentialDeposit
 				);
 
-				Self::ensure_can_withdraw(currency_id, from, amount)?;
+				Self::ensure_can_withdraw_with_reasons(currency_id, from, amount, WithdrawReasons::TRANSFER)?;
 
 				let allow_death = existence_requirement == ExistenceRequirement::AllowDeath;
-				let allow_death = allow_death && frame_system::Pallet::<T>::can_dec_provider(from);
+				let allow_death = allow_death && Self::can_remove_account(currency_id, from);
 				let would_be_dead = if from_account.total() < ed {
 					if from_account.total().is_zero() {
 						true
@@ -1011,6 +2832,95 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Increase the amount `delegate` is allowed to transfer from `owner`,
+	/// reserving the approval deposit from `owner` if the approval is new.
+	pub(crate) fn do_approve_transfer(
+		currency_id: T::CurrencyId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Approvals::<T>::try_mutate((owner, currency_id, delegate), |maybe_approval| -> DispatchResult {
+			let is_new = maybe_approval.is_none();
+			let mut approval = maybe_approval.take().unwrap_or_else(|| Approval {
+				amount: Zero::zero(),
+				deposit: T::ApprovalDeposits::get(&currency_id),
+			});
+			approval.amount = approval.amount.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+			if is_new {
+				<Self as MultiReservableCurrency<_>>::reserve(currency_id, owner, approval.deposit)?;
+			}
+			*maybe_approval = Some(approval);
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::ApprovedTransfer {
+			currency_id,
+			owner: owner.clone(),
+			delegate: delegate.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Remove the approval of `delegate` on `owner`, returning the deposit.
+	pub(crate) fn do_cancel_approval(
+		currency_id: T::CurrencyId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+	) -> DispatchResult {
+		let approval = Approvals::<T>::take((owner, currency_id, delegate)).ok_or(Error::<T>::Unapproved)?;
+		<Self as MultiReservableCurrency<_>>::unreserve(currency_id, owner, approval.deposit);
+
+		Self::deposit_event(Event::ApprovalCancelled {
+			currency_id,
+			owner: owner.clone(),
+			delegate: delegate.clone(),
+		});
+		Ok(())
+	}
+
+	/// Transfer `amount` of `owner` to `destination` on behalf of `delegate`,
+	/// reducing the approval. The deposit is returned once the approval is
+	/// used up.
+	pub(crate) fn do_transfer_approved(
+		currency_id: T::CurrencyId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		destination: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Approvals::<T>::try_mutate_exists((owner, currency_id, delegate), |maybe_approval| -> DispatchResult {
+			let mut approval = maybe_approval.take().ok_or(Error::<T>::Unapproved)?;
+			let remaining = approval.amount.checked_sub(&amount).ok_or(Error::<T>::Unapproved)?;
+
+			Self::do_transfer(
+				currency_id,
+				owner,
+				destination,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			if remaining.is_zero() {
+				<Self as MultiReservableCurrency<_>>::unreserve(currency_id, owner, approval.deposit);
+			} else {
+				approval.amount = remaining;
+				*maybe_approval = Some(approval);
+			}
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::TransferredApproved {
+			currency_id,
+			owner: owner.clone(),
+			delegate: delegate.clone(),
+			destination: destination.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
 	/// Withdraw some free balance from an account, respecting existence
 	/// requirements.
 	///
@@ -1025,13 +2935,34 @@ impl<T: Config> Pallet<T> {
 		amount: T::Balance,
 		existence_requirement: ExistenceRequirement,
 		change_total_issuance: bool,
+	) -> DispatchResult {
+		Self::do_withdraw_with_reasons(
+			currency_id,
+			who,
+			amount,
+			existence_requirement,
+			change_total_issuance,
+			WithdrawReasons::all(),
+		)
+	}
+
+	/// Same as `do_withdraw`, but only the locks which prevent withdrawals
+	/// for any of `reasons` are respected.
+	pub(crate) fn do_withdraw_with_reasons(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		existence_requirement: ExistenceRequirement,
+		change_total_issuance: bool,
+		reasons: WithdrawReasons,
 	) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
 
+		Self::ensure_not_frozen(currency_id, who)?;
 		Self::try_mutate_account(who, currency_id, |account, _existed| -> DispatchResult {
-			Self::ensure_can_withdraw(currency_id, who, amount)?;
+			Self::ensure_can_withdraw_with_reasons(currency_id, who, amount, reasons)?;
 			let previous_total = account.total();
 			account.free = account.free.defensive_saturating_sub(amount);
 
@@ -1054,7 +2985,7 @@ impl<T: Config> Pallet<T> {
 			);
 
 			if change_total_issuance {
-				TotalIssuance::<T>::mutate(currency_id, |v| *v = v.defensive_saturating_sub(amount));
+				Self::mutate_total_issuance(currency_id, |v| *v = v.defensive_saturating_sub(amount));
 			}
 
 			Self::deposit_event(Event::Withdrawn {
@@ -1090,6 +3021,7 @@ impl<T: Config> Pallet<T> {
 			return Ok(amount);
 		}
 
+		Self::ensure_compliant(currency_id, who)?;
 		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PreDeposit::on_deposit(
 			currency_id,
 			who,
@@ -1106,13 +3038,22 @@ impl<T: Config> Pallet<T> {
 					amount >= ed || existed || T::DustRemovalWhitelist::contains(who),
 					Error::<T>::ExistentialDeposit
 				);
+				if !existed {
+					Self::ensure_can_create(currency_id, who)?;
+				}
 			}
 
+			// the total issuance is changed later by the imbalance if not now, so the
+			// cap is checked either way
 			let new_total_issuance = Self::total_issuance(currency_id)
 				.checked_add(&amount)
 				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				new_total_issuance <= Self::max_total_issuance(currency_id),
+				Error::<T>::MaxTotalIssuanceExceeded
+			);
 			if change_total_issuance {
-				TotalIssuance::<T>::mutate(currency_id, |v| *v = new_total_issuance);
+				Self::mutate_total_issuance(currency_id, |v| *v = new_total_issuance);
 			}
 			account.free = account.free.defensive_saturating_add(amount);
 			Ok(())
@@ -1233,7 +3174,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 
 		// Cannot underflow because the slashed value cannot be greater than total
 		// issuance but just to be defensive here.
-		TotalIssuance::<T>::mutate(currency_id, |v| {
+		Self::mutate_total_issuance(currency_id, |v| {
 			*v = v.defensive_saturating_sub(amount.defensive_saturating_sub(remaining_slash))
 		});
 
@@ -1284,92 +3225,177 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if amount.is_zero() {
-			return Ok(());
-		}
-		let mut new_lock = Some(BalanceLock { id: lock_id, amount });
-		let mut locks = Self::locks(who, currency_id)
-			.into_iter()
-			.filter_map(|lock| {
-				if lock.id == lock_id {
-					new_lock.take()
-				} else {
-					Some(lock)
-				}
-			})
-			.collect::<Vec<_>>();
-		if let Some(lock) = new_lock {
-			locks.push(lock)
-		}
-		Self::update_locks(currency_id, who, &locks[..])?;
+		Self::do_set_lock(lock_id, currency_id, who, amount, None, WithdrawReasons::all())
+	}
 
-		Self::deposit_event(Event::LockSet {
+	// Extend a lock on the balance of `who` under `currency_id`.
+	// Is a no-op if lock amount is zero
+	fn extend_lock(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::do_extend_lock(lock_id, currency_id, who, amount, None, WithdrawReasons::all())
+	}
+
+	// Set a lock on the balance of `who` under `currency_id` which prevents
+	// withdrawals for `reasons`.
+	// Is a no-op if lock amount is zero, removes the lock if `reasons` are
+	// empty.
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::do_set_lock(lock_id, currency_id, who, amount, None, reasons)
+	}
+
+	// Extend a lock on the balance of `who` under `currency_id` to also
+	// prevent withdrawals for `reasons`.
+	// Is a no-op if lock amount or `reasons` are zero.
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::do_extend_lock(lock_id, currency_id, who, amount, None, reasons)
+	}
+
+	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		let mut locks = Self::locks(who, currency_id);
+		locks.retain(|lock| lock.id != lock_id);
+		let locks_vec = locks.to_vec();
+		Self::update_locks(currency_id, who, &locks_vec[..])?;
+
+		Self::deposit_event(Event::LockRemoved {
 			lock_id,
 			currency_id,
 			who: who.clone(),
-			amount,
 		});
 		Ok(())
 	}
+}
 
-	// Extend a lock on the balance of `who` under `currency_id`.
-	// Is a no-op if lock amount is zero
-	fn extend_lock(
+impl<T: Config> MultiExpiringLockableCurrency<T::AccountId> for Pallet<T> {
+	// Set a lock on the balance of `who` under `currency_id` until `until`.
+	// Is a no-op if lock amount is zero or `until` has passed.
+	fn set_lock_until(
 		lock_id: LockIdentifier,
 		currency_id: Self::CurrencyId,
 		who: &T::AccountId,
 		amount: Self::Balance,
+		until: Self::Moment,
 	) -> DispatchResult {
-		if amount.is_zero() {
+		if until <= frame_system::Pallet::<T>::block_number() {
 			return Ok(());
 		}
-		let mut new_lock = Some(BalanceLock { id: lock_id, amount });
-		let mut locks = Self::locks(who, currency_id)
-			.into_iter()
-			.filter_map(|lock| {
-				if lock.id == lock_id {
-					new_lock.take().map(|nl| {
-						let new_amount = lock.amount.max(nl.amount);
-						Self::deposit_event(Event::LockSet {
-							lock_id,
-							currency_id,
-							who: who.clone(),
-							amount: new_amount,
-						});
-						BalanceLock {
-							id: lock.id,
-							amount: new_amount,
-						}
-					})
-				} else {
-					Some(lock)
-				}
-			})
-			.collect::<Vec<_>>();
-		if let Some(lock) = new_lock {
-			Self::deposit_event(Event::LockSet {
-				lock_id,
-				currency_id,
-				who: who.clone(),
-				amount: lock.amount,
-			});
-			locks.push(lock)
+		Self::do_set_lock(lock_id, currency_id, who, amount, Some(until), WithdrawReasons::all())
+	}
+
+	// Extend a lock on the balance of `who` under `currency_id` until `until`.
+	// Is a no-op if lock amount is zero or `until` has passed.
+	fn extend_lock_until(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		until: Self::Moment,
+	) -> DispatchResult {
+		if until <= frame_system::Pallet::<T>::block_number() {
+			return Ok(());
 		}
-		Self::update_locks(currency_id, who, &locks[..])
+		Self::do_extend_lock(lock_id, currency_id, who, amount, Some(until), WithdrawReasons::all())
+	}
+}
+
+impl<T: Config> orml_traits::MultiCurrencyImbalance<T::AccountId> for Pallet<T> {
+	type PositiveImbalance = MultiPositiveImbalance<T>;
+	type NegativeImbalance = MultiNegativeImbalance<T>;
+
+	fn issue(currency_id: Self::CurrencyId, mut amount: Self::Balance) -> Self::NegativeImbalance {
+		if amount.is_zero() {
+			return MultiNegativeImbalance::zero(currency_id);
+		}
+		let max_total_issuance = Self::max_total_issuance(currency_id);
+		Self::mutate_total_issuance(currency_id, |issued| {
+			*issued = issued
+				.checked_add(&amount)
+				.filter(|new_issued| *new_issued <= max_total_issuance)
+				.unwrap_or_else(|| {
+					amount = max_total_issuance.saturating_sub(*issued);
+					max_total_issuance.max(*issued)
+				})
+		});
+
+		Self::deposit_event(Event::TotalIssuanceSet {
+			currency_id,
+			amount: Self::total_issuance(currency_id),
+		});
+		MultiNegativeImbalance::new(currency_id, amount)
 	}
 
-	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
-		let mut locks = Self::locks(who, currency_id);
-		locks.retain(|lock| lock.id != lock_id);
-		let locks_vec = locks.to_vec();
-		Self::update_locks(currency_id, who, &locks_vec[..])?;
+	fn burn(currency_id: Self::CurrencyId, mut amount: Self::Balance) -> Self::PositiveImbalance {
+		if amount.is_zero() {
+			return MultiPositiveImbalance::zero(currency_id);
+		}
+		Self::mutate_total_issuance(currency_id, |issued| {
+			*issued = issued.checked_sub(&amount).unwrap_or_else(|| {
+				amount = *issued;
+				Zero::zero()
+			})
+		});
 
-		Self::deposit_event(Event::LockRemoved {
-			lock_id,
+		Self::deposit_event(Event::TotalIssuanceSet {
 			currency_id,
-			who: who.clone(),
+			amount: Self::total_issuance(currency_id),
 		});
-		Ok(())
+		MultiPositiveImbalance::new(currency_id, amount)
+	}
+
+	fn deposit_creating(
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::PositiveImbalance {
+		// do not change total issuance
+		Self::do_deposit(currency_id, who, value, false, false).map_or_else(
+			|_| MultiPositiveImbalance::zero(currency_id),
+			|_| MultiPositiveImbalance::new(currency_id, value),
+		)
+	}
+
+	fn withdraw(
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+		liveness: ExistenceRequirement,
+	) -> Result<Self::NegativeImbalance, DispatchError> {
+		// do not change total issuance
+		Self::do_withdraw(currency_id, who, value, liveness, false)
+			.map(|_| MultiNegativeImbalance::new(currency_id, value))
+	}
+
+	fn settle(
+		who: &T::AccountId,
+		imbalance: Self::PositiveImbalance,
+		liveness: ExistenceRequirement,
+	) -> Result<(), Self::PositiveImbalance> {
+		let currency_id = imbalance.currency_id();
+		let amount = imbalance.peek();
+		match <Self as orml_traits::MultiCurrencyImbalance<T::AccountId>>::withdraw(currency_id, who, amount, liveness)
+		{
+			Ok(negative) => {
+				// both sides are of `currency_id` and `amount`, so they cancel out
+				let _ = imbalance.offset(negative);
+				Ok(())
+			}
+			Err(_) => Err(imbalance),
+		}
 	}
 }
 
@@ -1381,38 +3407,16 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		if value.is_zero() {
 			return true;
 		}
-		Self::ensure_can_withdraw(currency_id, who, value).is_ok()
+		Self::ensure_not_frozen(currency_id, who).is_ok()
+			&& Self::ensure_can_withdraw_with_reasons(currency_id, who, value, WithdrawReasons::RESERVE).is_ok()
 	}
 
-	/// Slash from reserved balance, returning any amount that was unable to
-	/// be slashed.
+	/// Slash from reserved balance which is neither named nor held, returning
+	/// any amount that was unable to be slashed.
 	///
 	/// Is a no-op if the value to be slashed is zero.
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if value.is_zero() {
-			return value;
-		}
-
-		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnSlash::on_slash(
-			currency_id,
-			who,
-			value,
-		);
-		let reserved_balance = Self::reserved_balance(currency_id, who);
-		let actual = reserved_balance.min(value);
-		Self::mutate_account(who, currency_id, |account, _| {
-			// ensured reserved_balance >= actual but just to be defensive here.
-			account.reserved = reserved_balance.defensive_saturating_sub(actual);
-		});
-		TotalIssuance::<T>::mutate(currency_id, |v| *v = v.defensive_saturating_sub(actual));
-
-		Self::deposit_event(Event::Slashed {
-			currency_id,
-			who: who.clone(),
-			free_amount: Zero::zero(),
-			reserved_amount: actual,
-		});
-		value.defensive_saturating_sub(actual)
+		Self::do_slash_reserved(currency_id, who, value, false)
 	}
 
 	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
@@ -1427,7 +3431,8 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		if value.is_zero() {
 			return Ok(());
 		}
-		Self::ensure_can_withdraw(currency_id, who, value)?;
+		Self::ensure_not_frozen(currency_id, who)?;
+		Self::ensure_can_withdraw_with_reasons(currency_id, who, value, WithdrawReasons::RESERVE)?;
 
 		Self::mutate_account(who, currency_id, |account, _| {
 			account.free = account.free.defensive_saturating_sub(value);
@@ -1443,33 +3448,16 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
-	/// Unreserve some funds, returning any amount that was unable to be
-	/// unreserved.
+	/// Unreserve some funds which are neither named nor held, returning any
+	/// amount that was unable to be unreserved.
 	///
 	/// Is a no-op if the value to be unreserved is zero.
 	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if value.is_zero() {
-			return value;
-		}
-
-		let (remaining, _) = Self::mutate_account(who, currency_id, |account, _| {
-			let actual = account.reserved.min(value);
-			account.reserved = account.reserved.defensive_saturating_sub(actual);
-			account.free = account.free.defensive_saturating_add(actual);
-
-			Self::deposit_event(Event::Unreserved {
-				currency_id,
-				who: who.clone(),
-				amount: actual,
-			});
-			value.defensive_saturating_sub(actual)
-		});
-
-		remaining
+		Self::do_unreserve(currency_id, who, value, false)
 	}
 
-	/// Move the reserved balance of one account into the balance of
-	/// another, according to `status`.
+	/// Move the reserved balance which is neither named nor held of one
+	/// account into the balance of another, according to `status`.
 	///
 	/// Is a no-op if:
 	/// - the value to be moved is zero; or
@@ -1482,50 +3470,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		value: Self::Balance,
 		status: BalanceStatus,
 	) -> sp_std::result::Result<Self::Balance, DispatchError> {
-		if value.is_zero() {
-			return Ok(value);
-		}
-
-		if slashed == beneficiary {
-			return match status {
-				BalanceStatus::Free => Ok(Self::unreserve(currency_id, slashed, value)),
-				BalanceStatus::Reserved => Ok(value.saturating_sub(Self::reserved_balance(currency_id, slashed))),
-			};
-		}
-
-		let from_account = Self::accounts(slashed, currency_id);
-		let to_account = Self::accounts(beneficiary, currency_id);
-		let actual = from_account.reserved.min(value);
-		match status {
-			BalanceStatus::Free => {
-				Self::set_free_balance(
-					currency_id,
-					beneficiary,
-					to_account.free.defensive_saturating_add(actual),
-				);
-			}
-			BalanceStatus::Reserved => {
-				Self::set_reserved_balance(
-					currency_id,
-					beneficiary,
-					to_account.reserved.defensive_saturating_add(actual),
-				);
-			}
-		}
-		Self::set_reserved_balance(
-			currency_id,
-			slashed,
-			from_account.reserved.defensive_saturating_sub(actual),
-		);
-
-		Self::deposit_event(Event::<T>::ReserveRepatriated {
-			currency_id,
-			from: slashed.clone(),
-			to: beneficiary.clone(),
-			amount: actual,
-			status,
-		});
-		Ok(value.defensive_saturating_sub(actual))
+		Self::do_repatriate_reserved(currency_id, slashed, beneficiary, value, status, false)
 	}
 }
 
@@ -1570,7 +3515,15 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 						.map_err(|_| Error::<T>::TooManyReserves)?;
 				}
 			};
-			<Self as MultiReservableCurrency<_>>::reserve(currency_id, who, value)
+			<Self as MultiReservableCurrency<_>>::reserve(currency_id, who, value)?;
+
+			Self::deposit_event(Event::ReservedNamed {
+				id: *id,
+				currency_id,
+				who: who.clone(),
+				amount: value,
+			});
+			Ok(())
 		})
 	}
 
@@ -1594,7 +3547,7 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 					Ok(index) => {
 						let to_change = cmp::min(reserves[index].amount, value);
 
-						let remain = <Self as MultiReservableCurrency<_>>::unreserve(currency_id, who, to_change);
+						let remain = Self::do_unreserve(currency_id, who, to_change, true);
 
 						// remain should always be zero but just to be defensive here.
 						let actual = to_change.defensive_saturating_sub(remain);
@@ -1603,6 +3556,13 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 						// here.
 						reserves[index].amount = reserves[index].amount.defensive_saturating_sub(actual);
 
+						Self::deposit_event(Event::UnreservedNamed {
+							id: *id,
+							currency_id,
+							who: who.clone(),
+							amount: actual,
+						});
+
 						if reserves[index].amount.is_zero() {
 							if reserves.len() == 1 {
 								// no more named reserves
@@ -1641,7 +3601,7 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 				Ok(index) => {
 					let to_change = cmp::min(reserves[index].amount, value);
 
-					let remain = <Self as MultiReservableCurrency<_>>::slash_reserved(currency_id, who, to_change);
+					let remain = Self::do_slash_reserved(currency_id, who, to_change, true);
 
 					// remain should always be zero but just to be defensive here.
 					let actual = to_change.defensive_saturating_sub(remain);
@@ -1690,6 +3650,13 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 			};
 		}
 
+		Self::ensure_not_frozen(currency_id, slashed)?;
+		Self::ensure_compliant(currency_id, slashed)?;
+		Self::ensure_compliant(currency_id, beneficiary)?;
+		if !Accounts::<T>::contains_key(beneficiary, currency_id) {
+			Self::ensure_can_create(currency_id, beneficiary)?;
+		}
+
 		Reserves::<T>::try_mutate(
 			slashed,
 			currency_id,
@@ -1706,12 +3673,13 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 								|reserves| -> Result<T::Balance, DispatchError> {
 									match reserves.binary_search_by_key(id, |data| data.id) {
 										Ok(index) => {
-											let remain = <Self as MultiReservableCurrency<_>>::repatriate_reserved(
+											let remain = Self::do_repatriate_reserved(
 												currency_id,
 												slashed,
 												beneficiary,
 												to_change,
 												status,
+												true,
 											)?;
 
 											// remain should always be zero but just to be defensive
@@ -1725,12 +3693,13 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 											Ok(actual)
 										}
 										Err(index) => {
-											let remain = <Self as MultiReservableCurrency<_>>::repatriate_reserved(
+											let remain = Self::do_repatriate_reserved(
 												currency_id,
 												slashed,
 												beneficiary,
 												to_change,
 												status,
+												true,
 											)?;
 
 											// remain should always be zero but just to be defensive
@@ -1753,12 +3722,13 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 								},
 							)?
 						} else {
-							let remain = <Self as MultiReservableCurrency<_>>::repatriate_reserved(
+							let remain = Self::do_repatriate_reserved(
 								currency_id,
 								slashed,
 								beneficiary,
 								to_change,
 								status,
+								true,
 							)?;
 
 							// remain should always be zero but just to be defensive here
@@ -1768,6 +3738,15 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 						// `actual <= to_change` and `to_change <= amount` but just to be defensive
 						// here.
 						reserves[index].amount = reserves[index].amount.defensive_saturating_sub(actual);
+
+						Self::deposit_event(Event::ReserveRepatriatedNamed {
+							id: *id,
+							currency_id,
+							from: slashed.clone(),
+							to: beneficiary.clone(),
+							amount: actual,
+							status,
+						});
 						Ok(value.defensive_saturating_sub(actual))
 					}
 					Err(_) => Ok(value),
@@ -1803,10 +3782,20 @@ impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
 		preservation: Preservation,
 		_force: Fortitude,
 	) -> Self::Balance {
+		if Self::ensure_not_frozen(asset_id, who).is_err() {
+			return Zero::zero();
+		}
+
 		let a = Self::accounts(who, asset_id);
-		// Liquid balance is what is neither reserved nor locked/frozen.
-		let liquid = a.free.saturating_sub(a.frozen);
-		if frame_system::Pallet::<T>::can_dec_provider(who) && !matches!(preservation, Preservation::Protect) {
+		// Liquid balance is what is neither reserved nor locked/frozen, the reducible
+		// balance is transferable.
+		let liquid = a.free.saturating_sub(Self::frozen_balance_with_reasons(
+			asset_id,
+			who,
+			&a,
+			WithdrawReasons::TRANSFER,
+		));
+		if Self::can_remove_account(asset_id, who) && !matches!(preservation, Preservation::Protect) {
 			liquid
 		} else {
 			// `must_remain_to_exist` is the part of liquid balance which must remain to
@@ -1899,29 +3888,36 @@ impl<T: Config> fungibles::Unbalanced<T::AccountId> for Pallet<T> {
 		);
 
 		// Balance is the same type and will not overflow
-		let (_, dust_amount) = Self::try_mutate_account(who, asset_id, |account, _| -> Result<(), DispatchError> {
-			// Make sure the reduction (if there is one) is no more than the maximum
-			// allowed.
-			let reduction = account.free.saturating_sub(amount);
-			ensure!(reduction <= max_reduction, Error::<T>::BalanceTooLow);
+		let (_, dust_amount) =
+			Self::try_mutate_account(who, asset_id, |account, existed| -> Result<(), DispatchError> {
+				if !existed {
+					Self::ensure_can_create(asset_id, who)?;
+				}
 
-			account.free = amount;
-			Self::deposit_event(Event::BalanceSet {
-				currency_id: asset_id,
-				who: who.clone(),
-				free: account.free,
-				reserved: account.reserved,
-			});
+				// Make sure the reduction (if there is one) is no more than the maximum
+				// allowed.
+				let reduction = account.free.saturating_sub(amount);
+				ensure!(reduction <= max_reduction, Error::<T>::BalanceTooLow);
 
-			Ok(())
-		})?;
+				account.free = amount;
+				Self::deposit_event(Event::BalanceSet {
+					currency_id: asset_id,
+					who: who.clone(),
+					free: account.free,
+					reserved: account.reserved,
+				});
+
+				Ok(())
+			})?;
 
 		Ok(dust_amount)
 	}
 
 	fn set_total_issuance(asset_id: Self::AssetId, amount: Self::Balance) {
+		// never exceed the maximum total issuance of the asset
+		let amount = amount.min(Self::max_total_issuance(asset_id));
 		// Balance is the same type and will not overflow
-		TotalIssuance::<T>::mutate(asset_id, |t| *t = amount);
+		Self::mutate_total_issuance(asset_id, |t| *t = amount);
 
 		Self::deposit_event(Event::TotalIssuanceSet {
 			currency_id: asset_id,
@@ -1978,12 +3974,18 @@ impl<T: Config> fungibles::Balanced<T::AccountId> for Pallet<T> {
 
 type ReasonOf<P, T> = <P as fungibles::InspectHold<<T as frame_system::Config>::AccountId>>::Reason;
 impl<T: Config> fungibles::InspectHold<T::AccountId> for Pallet<T> {
-	type Reason = ();
+	type Reason = T::RuntimeHoldReason;
 
-	fn balance_on_hold(asset_id: Self::AssetId, _reason: &Self::Reason, who: &T::AccountId) -> T::Balance {
-		Self::accounts(who, asset_id).reserved
+	fn balance_on_hold(asset_id: Self::AssetId, reason: &Self::Reason, who: &T::AccountId) -> T::Balance {
+		let holds = Self::holds(who, asset_id);
+		holds
+			.binary_search_by_key(reason, |data| data.id)
+			.map(|index| holds[index].amount)
+			.unwrap_or_default()
 	}
 
+	/// The whole reserved balance of `who`, like `pallet_balances` does,
+	/// including the named and anonymous reserves as well as the holds.
 	fn total_balance_on_hold(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
 		Self::accounts(who, asset).reserved
 	}
@@ -1992,13 +3994,24 @@ impl<T: Config> fungibles::InspectHold<T::AccountId> for Pallet<T> {
 		0u32.into()
 	}
 
-	fn hold_available(_asset: Self::AssetId, _reason: &Self::Reason, _who: &T::AccountId) -> bool {
-		true
+	fn hold_available(asset: Self::AssetId, reason: &Self::Reason, who: &T::AccountId) -> bool {
+		let holds = Self::holds(who, asset);
+		holds.binary_search_by_key(reason, |data| data.id).is_ok() || holds.len() < T::MaxHolds::get() as usize
 	}
 
-	fn can_hold(asset_id: Self::AssetId, _reason: &Self::Reason, who: &T::AccountId, amount: T::Balance) -> bool {
+	fn can_hold(asset_id: Self::AssetId, reason: &Self::Reason, who: &T::AccountId, amount: T::Balance) -> bool {
+		if Self::ensure_not_frozen(asset_id, who).is_err()
+			|| !<Self as fungibles::InspectHold<_>>::hold_available(asset_id, reason, who)
+		{
+			return false;
+		}
 		let a = Self::accounts(who, asset_id);
-		let min_balance = T::ExistentialDeposits::get(&asset_id).max(a.frozen);
+		let min_balance = T::ExistentialDeposits::get(&asset_id).max(Self::frozen_balance_with_reasons(
+			asset_id,
+			who,
+			&a,
+			WithdrawReasons::RESERVE,
+		));
 		if a.reserved.checked_add(&amount).is_none() {
 			return false;
 		}
@@ -2016,16 +4029,27 @@ impl<T: Config> fungibles::InspectHold<T::AccountId> for Pallet<T> {
 impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T> {
 	fn hold(
 		asset_id: Self::AssetId,
-		_reason: &ReasonOf<Self, T>,
+		reason: &ReasonOf<Self, T>,
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		<Pallet<T> as MultiReservableCurrency<_>>::reserve(asset_id, who, amount)
+		if amount.is_zero() {
+			return Ok(());
+		}
+		ensure!(
+			<Self as fungibles::InspectHold<_>>::hold_available(asset_id, reason, who),
+			Error::<T>::TooManyHolds
+		);
+
+		let held = <Self as fungibles::InspectHold<_>>::balance_on_hold(asset_id, reason, who);
+		<Pallet<T> as MultiReservableCurrency<_>>::reserve(asset_id, who, amount)?;
+		// the hold is available and the reserve succeeded, so this can't fail.
+		Self::set_hold(asset_id, who, reason, held.defensive_saturating_add(amount))
 	}
 
 	fn release(
 		asset_id: Self::AssetId,
-		_reason: &ReasonOf<Self, T>,
+		reason: &ReasonOf<Self, T>,
 		who: &T::AccountId,
 		amount: Self::Balance,
 		precision: Precision,
@@ -2034,6 +4058,14 @@ impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T> {
 			return Ok(amount);
 		}
 
+		// Only the funds held for `reason` can be released.
+		let held = <Self as fungibles::InspectHold<_>>::balance_on_hold(asset_id, reason, who);
+		ensure!(
+			matches!(precision, Precision::BestEffort) || amount <= held,
+			Error::<T>::BalanceTooLow
+		);
+		let amount = amount.min(held);
+
 		// Done on a best-effort basis.
 		let (released, _) =
 			Self::try_mutate_account(who, asset_id, |a, _existed| -> Result<T::Balance, DispatchError> {
@@ -2047,10 +4079,12 @@ impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T> {
 				a.free = new_free;
 				a.reserved = a.reserved.saturating_sub(actual);
 
+				Self::set_hold(asset_id, who, reason, held.defensive_saturating_sub(actual))?;
+
 				Self::deposit_event(Event::Unreserved {
 					currency_id: asset_id,
 					who: who.clone(),
-					amount,
+					amount: actual,
 				});
 				Ok(actual)
 			})?;
@@ -2073,36 +4107,60 @@ impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T> {
 		} else {
 			Status::Free
 		};
+
+		// Only the funds held for `reason` can be transferred.
+		let held = <Self as fungibles::InspectHold<T::AccountId>>::balance_on_hold(asset_id, reason, source);
 		ensure!(
-			amount <= <Self as fungibles::InspectHold<T::AccountId>>::balance_on_hold(asset_id, reason, source)
-				|| precision == Precision::BestEffort,
+			amount <= held || precision == Precision::BestEffort,
 			Error::<T>::BalanceTooLow
 		);
-		let gap = Self::repatriate_reserved(asset_id, source, dest, amount, status)?;
-		// return actual transferred amount
-		Ok(amount.saturating_sub(gap))
+		ensure!(
+			status == Status::Free || <Self as fungibles::InspectHold<_>>::hold_available(asset_id, reason, dest),
+			Error::<T>::TooManyHolds
+		);
+
+		let amount = amount.min(held);
+		let gap = Self::do_repatriate_reserved(asset_id, source, dest, amount, status, true)?;
+		// actual transferred amount
+		let actual = amount.saturating_sub(gap);
+
+		Self::set_hold(asset_id, source, reason, held.defensive_saturating_sub(actual))?;
+		if status == Status::Reserved {
+			let dest_held = <Self as fungibles::InspectHold<_>>::balance_on_hold(asset_id, reason, dest);
+			Self::set_hold(asset_id, dest, reason, dest_held.defensive_saturating_add(actual))?;
+		}
+
+		Ok(actual)
 	}
 }
 
 impl<T: Config> fungibles::UnbalancedHold<T::AccountId> for Pallet<T> {
 	fn set_balance_on_hold(
 		asset: Self::AssetId,
-		_reason: &Self::Reason,
+		reason: &Self::Reason,
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
+		let old_held = <Self as fungibles::InspectHold<_>>::balance_on_hold(asset, reason, who);
+
 		// Balance is the same type and will not overflow
 		Self::try_mutate_account(who, asset, |account, _| -> Result<(), DispatchError> {
-			let old_reserved = account.reserved;
-			account.reserved = amount;
+			// reserved = reserved - old + new
+			account.reserved = account
+				.reserved
+				.defensive_saturating_sub(old_held)
+				.checked_add(&amount)
+				.ok_or(ArithmeticError::Overflow)?;
 			// free = free + old - new
 			account.free = account
 				.free
-				.checked_add(&old_reserved)
+				.checked_add(&old_held)
 				.ok_or(ArithmeticError::Overflow)?
-				.checked_sub(&account.reserved)
+				.checked_sub(&amount)
 				.ok_or(TokenError::BelowMinimum)?;
 
+			Self::set_hold(asset, who, reason, amount)?;
+
 			Self::deposit_event(Event::BalanceSet {
 				currency_id: asset,
 				who: who.clone(),
@@ -2116,6 +4174,106 @@ impl<T: Config> fungibles::UnbalancedHold<T::AccountId> for Pallet<T> {
 	}
 }
 
+impl<T: Config> fungibles::InspectFreeze<T::AccountId> for Pallet<T> {
+	type Id = T::FreezeIdentifier;
+
+	fn balance_frozen(asset: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> Self::Balance {
+		let freezes = Self::freezes(who, asset);
+		freezes
+			.binary_search_by_key(id, |data| data.id)
+			.map(|index| freezes[index].amount)
+			.unwrap_or_default()
+	}
+
+	fn can_freeze(asset: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Self::freezes(who, asset);
+		freezes.binary_search_by_key(id, |data| data.id).is_ok() || freezes.len() < T::MaxFreezes::get() as usize
+	}
+}
+
+impl<T: Config> fungibles::MutateFreeze<T::AccountId> for Pallet<T> {
+	// Set a freeze on the balance of `who` under `asset`.
+	// Is equivalent to `thaw` if the amount is zero.
+	fn set_freeze(asset: Self::AssetId, id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return <Self as fungibles::MutateFreeze<_>>::thaw(asset, id, who);
+		}
+		let mut freezes = Self::freezes(who, asset);
+		match freezes.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => {
+				freezes[index].amount = amount;
+			}
+			Err(index) => {
+				freezes
+					.try_insert(index, IdAmount { id: *id, amount })
+					.map_err(|_| Error::<T>::TooManyFreezes)?;
+			}
+		};
+		Self::update_freezes(asset, who, &freezes[..])?;
+
+		Self::deposit_event(Event::FreezeSet {
+			freeze_id: *id,
+			currency_id: asset,
+			who: who.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	// Extend a freeze on the balance of `who` under `asset`.
+	// Is a no-op if the amount is zero.
+	fn extend_freeze(asset: Self::AssetId, id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		let current = <Self as fungibles::InspectFreeze<_>>::balance_frozen(asset, id, who);
+		<Self as fungibles::MutateFreeze<_>>::set_freeze(asset, id, who, current.max(amount))
+	}
+
+	fn thaw(asset: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Self::freezes(who, asset);
+		freezes.retain(|freeze| freeze.id != *id);
+		let freezes_vec = freezes.to_vec();
+		Self::update_freezes(asset, who, &freezes_vec[..])?;
+
+		Self::deposit_event(Event::FreezeRemoved {
+			freeze_id: *id,
+			currency_id: asset,
+			who: who.clone(),
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config> fungibles::approvals::Inspect<T::AccountId> for Pallet<T> {
+	fn allowance(asset: Self::AssetId, owner: &T::AccountId, delegate: &T::AccountId) -> Self::Balance {
+		Self::approvals((owner, asset, delegate))
+			.map(|approval| approval.amount)
+			.unwrap_or_default()
+	}
+}
+
+impl<T: Config> fungibles::approvals::Mutate<T::AccountId> for Pallet<T> {
+	fn approve(
+		asset: Self::AssetId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::do_approve_transfer(asset, owner, delegate, amount)
+	}
+
+	fn transfer_from(
+		asset: Self::AssetId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::do_transfer_approved(asset, owner, delegate, dest, amount)
+	}
+}
+
 pub struct CurrencyAdapter<T, GetCurrencyId>(marker::PhantomData<(T, GetCurrencyId)>);
 
 impl<T, GetCurrencyId> PalletCurrency<T::AccountId> for CurrencyAdapter<T, GetCurrencyId>
@@ -2148,7 +4306,7 @@ where
 			return PositiveImbalance::zero();
 		}
 		let currency_id = GetCurrencyId::get();
-		TotalIssuance::<T>::mutate(currency_id, |issued| {
+		Pallet::<T>::mutate_total_issuance(currency_id, |issued| {
 			*issued = issued.checked_sub(&amount).unwrap_or_else(|| {
 				amount = *issued;
 				Zero::zero()
@@ -2166,11 +4324,15 @@ where
 		if amount.is_zero() {
 			return NegativeImbalance::zero();
 		}
-		TotalIssuance::<T>::mutate(GetCurrencyId::get(), |issued| {
-			*issued = issued.checked_add(&amount).unwrap_or_else(|| {
-				amount = Self::Balance::max_value().defensive_saturating_sub(*issued);
-				Self::Balance::max_value()
-			})
+		let max_total_issuance = Pallet::<T>::max_total_issuance(GetCurrencyId::get());
+		Pallet::<T>::mutate_total_issuance(GetCurrencyId::get(), |issued| {
+			*issued = issued
+				.checked_add(&amount)
+				.filter(|new_issued| *new_issued <= max_total_issuance)
+				.unwrap_or_else(|| {
+					amount = max_total_issuance.saturating_sub(*issued);
+					max_total_issuance.max(*issued)
+				})
 		});
 
 		Pallet::<T>::deposit_event(Event::TotalIssuanceSet {
@@ -2187,10 +4349,10 @@ where
 	fn ensure_can_withdraw(
 		who: &T::AccountId,
 		amount: Self::Balance,
-		_reasons: WithdrawReasons,
+		reasons: WithdrawReasons,
 		_new_balance: Self::Balance,
 	) -> DispatchResult {
-		<Pallet<T> as MultiCurrency<_>>::ensure_can_withdraw(GetCurrencyId::get(), who, amount)
+		Pallet::<T>::ensure_can_withdraw_with_reasons(GetCurrencyId::get(), who, amount, reasons)
 	}
 
 	fn transfer(
@@ -2273,11 +4435,11 @@ where
 	fn withdraw(
 		who: &T::AccountId,
 		value: Self::Balance,
-		_reasons: WithdrawReasons,
+		reasons: WithdrawReasons,
 		liveness: ExistenceRequirement,
 	) -> sp_std::result::Result<Self::NegativeImbalance, DispatchError> {
 		// do not change total issuance
-		Pallet::<T>::do_withdraw(GetCurrencyId::get(), who, value, liveness, false)
+		Pallet::<T>::do_withdraw_with_reasons(GetCurrencyId::get(), who, value, liveness, false, reasons)
 			.map(|_| Self::NegativeImbalance::new(value))
 	}
 
@@ -2418,12 +4580,24 @@ where
 	type Moment = BlockNumberFor<T>;
 	type MaxLocks = ();
 
-	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, _reasons: WithdrawReasons) {
-		let _ = <Pallet<T> as MultiLockableCurrency<_>>::set_lock(id, GetCurrencyId::get(), who, amount);
+	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		let _ = <Pallet<T> as MultiLockableCurrency<_>>::set_lock_with_reasons(
+			id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		);
 	}
 
-	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, _reasons: WithdrawReasons) {
-		let _ = <Pallet<T> as MultiLockableCurrency<_>>::extend_lock(id, GetCurrencyId::get(), who, amount);
+	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		let _ = <Pallet<T> as MultiLockableCurrency<_>>::extend_lock_with_reasons(
+			id,
+			GetCurrencyId::get(),
+			who,
+			amount,
+			reasons,
+		);
 	}
 
 	fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
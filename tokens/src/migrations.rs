@@ -0,0 +1,631 @@
+//! Migrations of balances from other pallets into this pallet.
+//!
+//! [`MigrateToTokens`] copies the balances of a [`MigrationSource`] into
+//! `Accounts` and `TotalIssuance`. It is started by `on_runtime_upgrade` and
+//! continues in `on_idle` over multiple blocks, keeping a cursor into the
+//! storage of the source pallet. To use it, add it to the runtime migrations
+//! and to the `MultiBlockMigrations` of this pallet.
+//!
+//! [`MigrateToV1`] adds the expiry to the existing `Locks`, and
+//! [`MigrateToV2`] adds the withdraw reasons to them. They run over multiple
+//! blocks in the same way. [`MigrateToHolds`] seeds `Holds` with the holds
+//! made before they were tracked per reason.
+
+use crate::{BalanceLock, Config, LockReasons, Locks, Pallet};
+use frame_support::{
+	migration::{get_storage_value, storage_iter},
+	pallet_prelude::*,
+	storage::{storage_prefix, unhashed, PrefixIterator, StoragePrefixedMap},
+	traits::OnRuntimeUpgrade,
+	Blake2_128Concat, ReversibleStorageHasher,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use orml_traits::LockIdentifier;
+use sp_runtime::traits::{Convert, Saturating, Zero};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// A migration which runs over multiple blocks in `on_idle`.
+///
+/// Unlike `OnIdle`, the migrations of a tuple always run in order, so a later
+/// migration only gets the weight left over by the earlier ones.
+pub trait MultiBlockMigration<BlockNumber> {
+	/// Run the next step of the migration within `remaining_weight`, returning
+	/// the weight used.
+	fn on_idle(n: BlockNumber, remaining_weight: Weight) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<BlockNumber: Copy> MultiBlockMigration<BlockNumber> for Tuple {
+	#[allow(clippy::let_and_return)]
+	fn on_idle(n: BlockNumber, remaining_weight: Weight) -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!( #(
+			weight.saturating_accrue(Tuple::on_idle(n, remaining_weight.saturating_sub(weight)));
+		)* );
+		weight
+	}
+}
+
+/// The balance of an account in the source pallet.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SourceAccount<AccountId, CurrencyId, Balance> {
+	pub who: AccountId,
+	pub currency_id: CurrencyId,
+	pub free: Balance,
+	pub reserved: Balance,
+}
+
+/// A pallet whose balances can be migrated into this pallet.
+pub trait MigrationSource<AccountId, CurrencyId, Balance> {
+	/// The unique name of this source.
+	fn name() -> &'static str;
+
+	/// The total issuance of each currency in the source pallet.
+	fn total_issuance() -> Vec<(CurrencyId, Balance)>;
+
+	/// Return at most `limit` accounts after the raw storage key `cursor`,
+	/// starting from the first account if `cursor` is empty, together with
+	/// the cursor to continue from, or `None` if no account is left.
+	#[allow(clippy::type_complexity)]
+	fn accounts(cursor: Vec<u8>, limit: u32) -> (Vec<SourceAccount<AccountId, CurrencyId, Balance>>, Option<Vec<u8>>);
+}
+
+/// Iterate at most `limit` raw entries of `prefix` after `cursor`, skipping
+/// the entries which fail to decode.
+fn iter_from<T>(
+	prefix: Vec<u8>,
+	cursor: Vec<u8>,
+	limit: u32,
+	decode: impl Fn(&[u8], &[u8]) -> Result<T, parity_scale_codec::Error>,
+) -> (Vec<T>, Option<Vec<u8>>) {
+	let previous_key = if cursor.is_empty() { prefix.clone() } else { cursor };
+	let mut iter = PrefixIterator::<(Vec<u8>, Vec<u8>)>::new(prefix.clone(), previous_key, |raw_key, raw_value| {
+		Ok((raw_key.to_vec(), raw_value.to_vec()))
+	});
+
+	let mut items = Vec::new();
+	let mut last_key = None;
+	for _ in 0..limit {
+		match iter.next() {
+			Some((raw_key, raw_value)) => {
+				items.extend(decode(&raw_key, &raw_value).ok());
+				last_key = Some([&prefix[..], &raw_key[..]].concat());
+			}
+			None => return (items, None),
+		}
+	}
+	(items, last_key)
+}
+
+/// Balances in the storage of a `pallet_assets` instance named `PalletName`.
+///
+/// `AssetIdToCurrencyId` maps asset ids of `pallet_assets` to currency ids,
+/// the assets which are mapped to `None` are skipped. `pallet_assets` has no
+/// reserved balances, so only free balances are migrated.
+pub struct PalletAssets<PalletName, AssetId, AssetIdToCurrencyId>(
+	PhantomData<(PalletName, AssetId, AssetIdToCurrencyId)>,
+);
+impl<AccountId, CurrencyId, Balance, PalletName, AssetId, AssetIdToCurrencyId>
+	MigrationSource<AccountId, CurrencyId, Balance> for PalletAssets<PalletName, AssetId, AssetIdToCurrencyId>
+where
+	AccountId: Decode,
+	Balance: Decode + Zero,
+	PalletName: Get<&'static str>,
+	AssetId: Decode,
+	AssetIdToCurrencyId: Convert<AssetId, Option<CurrencyId>>,
+{
+	fn name() -> &'static str {
+		PalletName::get()
+	}
+
+	fn total_issuance() -> Vec<(CurrencyId, Balance)> {
+		// the supply is the fifth field of `AssetDetails`, after the owner, issuer,
+		// admin and freezer accounts
+		storage_iter::<(AccountId, AccountId, AccountId, AccountId, Balance)>(PalletName::get().as_bytes(), b"Asset")
+			.filter_map(|(raw_key, (_, _, _, _, supply))| {
+				let asset_id = AssetId::decode(&mut Blake2_128Concat::reverse(&raw_key)).ok()?;
+				AssetIdToCurrencyId::convert(asset_id).map(|currency_id| (currency_id, supply))
+			})
+			.collect()
+	}
+
+	fn accounts(cursor: Vec<u8>, limit: u32) -> (Vec<SourceAccount<AccountId, CurrencyId, Balance>>, Option<Vec<u8>>) {
+		let prefix = storage_prefix(PalletName::get().as_bytes(), b"Account").to_vec();
+		let (accounts, cursor) = iter_from(prefix, cursor, limit, |raw_key, mut raw_value| {
+			let mut key_material = Blake2_128Concat::reverse(raw_key);
+			let asset_id = AssetId::decode(&mut key_material)?;
+			let who = AccountId::decode(&mut Blake2_128Concat::reverse(key_material))?;
+			// the balance is the first field of `AssetAccount`
+			let free = Balance::decode(&mut raw_value)?;
+			Ok((asset_id, who, free))
+		});
+
+		let accounts = accounts
+			.into_iter()
+			.filter_map(|(asset_id, who, free)| {
+				AssetIdToCurrencyId::convert(asset_id).map(|currency_id| SourceAccount {
+					who,
+					currency_id,
+					free,
+					reserved: Zero::zero(),
+				})
+			})
+			.collect();
+		(accounts, cursor)
+	}
+}
+
+/// Balances in the storage of a `pallet_balances` instance named
+/// `PalletName`, which are migrated to `GetCurrencyId`.
+///
+/// Only works for `pallet_balances` instances which keep the account data in
+/// their own `Account` storage, rather than in `frame_system`.
+pub struct PalletBalances<PalletName, GetCurrencyId>(PhantomData<(PalletName, GetCurrencyId)>);
+impl<AccountId, CurrencyId, Balance, PalletName, GetCurrencyId> MigrationSource<AccountId, CurrencyId, Balance>
+	for PalletBalances<PalletName, GetCurrencyId>
+where
+	AccountId: Decode,
+	Balance: Decode + Zero,
+	PalletName: Get<&'static str>,
+	GetCurrencyId: Get<CurrencyId>,
+{
+	fn name() -> &'static str {
+		PalletName::get()
+	}
+
+	fn total_issuance() -> Vec<(CurrencyId, Balance)> {
+		let total_issuance = get_storage_value::<Balance>(PalletName::get().as_bytes(), b"TotalIssuance", &[])
+			.unwrap_or_else(Zero::zero);
+		sp_std::vec![(GetCurrencyId::get(), total_issuance)]
+	}
+
+	fn accounts(cursor: Vec<u8>, limit: u32) -> (Vec<SourceAccount<AccountId, CurrencyId, Balance>>, Option<Vec<u8>>) {
+		let prefix = storage_prefix(PalletName::get().as_bytes(), b"Account").to_vec();
+		let (accounts, cursor) = iter_from(prefix, cursor, limit, |raw_key, mut raw_value| {
+			let who = AccountId::decode(&mut Blake2_128Concat::reverse(raw_key))?;
+			// `AccountData` starts with the free and reserved balances
+			let (free, reserved) = <(Balance, Balance)>::decode(&mut raw_value)?;
+			Ok((who, free, reserved))
+		});
+
+		let accounts = accounts
+			.into_iter()
+			.map(|(who, free, reserved)| SourceAccount {
+				who,
+				currency_id: GetCurrencyId::get(),
+				free,
+				reserved,
+			})
+			.collect();
+		(accounts, cursor)
+	}
+}
+
+/// The progress of a migration.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum MigrationStatus {
+	/// The migration continues after the raw storage key of the source.
+	Ongoing { cursor: Vec<u8> },
+	/// The migration is completed.
+	Completed,
+}
+
+/// The status of the migrations, keyed by the name of the source.
+#[frame_support::storage_alias]
+pub type MigrationStatuses<T: Config> = StorageMap<Pallet<T>, Twox64Concat, Vec<u8>, MigrationStatus>;
+
+/// Copy the balances of `Source` into this pallet, at most `ItemsPerBlock`
+/// accounts per block.
+///
+/// The migrated balances are added to the existing balances of the accounts
+/// and to the total issuance. Accounts which end up below the existential
+/// deposit are dusted as usual.
+pub struct MigrateToTokens<T, Source, ItemsPerBlock>(PhantomData<(T, Source, ItemsPerBlock)>);
+
+impl<T, Source, ItemsPerBlock> MigrateToTokens<T, Source, ItemsPerBlock>
+where
+	T: Config,
+	Source: MigrationSource<T::AccountId, T::CurrencyId, T::Balance>,
+	ItemsPerBlock: Get<u32>,
+{
+	/// Migrate the next accounts within `weight_limit`.
+	pub fn step(weight_limit: Weight) -> Weight {
+		let mut weight = T::DbWeight::get().reads(1);
+		let cursor = match MigrationStatuses::<T>::get(Source::name().as_bytes()) {
+			Some(MigrationStatus::Ongoing { cursor }) => cursor,
+			_ => return weight,
+		};
+
+		// read the source account and the account, write the account, the system
+		// account and the total issuance
+		let weight_per_account = T::DbWeight::get().reads_writes(2, 3);
+		let limit = weight_limit
+			.saturating_sub(weight.saturating_add(T::DbWeight::get().writes(1)))
+			.checked_div_per_component(&weight_per_account)
+			// `None` if the weight per account is zero
+			.unwrap_or(u64::MAX)
+			.min(ItemsPerBlock::get().into()) as u32;
+		if limit == 0 {
+			return weight;
+		}
+
+		let (accounts, cursor) = Source::accounts(cursor, limit);
+		for account in accounts.iter() {
+			Pallet::<T>::mutate_total_issuance(account.currency_id, |total_issuance| {
+				*total_issuance = total_issuance
+					.saturating_add(account.free)
+					.saturating_add(account.reserved);
+			});
+			Pallet::<T>::mutate_account(&account.who, account.currency_id, |account_data, _| {
+				account_data.free = account_data.free.saturating_add(account.free);
+				account_data.reserved = account_data.reserved.saturating_add(account.reserved);
+			});
+		}
+		weight.saturating_accrue(weight_per_account.saturating_mul(limit.into()));
+
+		let status = match cursor {
+			Some(cursor) => MigrationStatus::Ongoing { cursor },
+			None => {
+				log::info!(target: "tokens", "migration from {} completed", Source::name());
+				MigrationStatus::Completed
+			}
+		};
+		MigrationStatuses::<T>::insert(Source::name().as_bytes(), status);
+		weight.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
+
+impl<T, Source, ItemsPerBlock> OnRuntimeUpgrade for MigrateToTokens<T, Source, ItemsPerBlock>
+where
+	T: Config,
+	Source: MigrationSource<T::AccountId, T::CurrencyId, T::Balance>,
+	ItemsPerBlock: Get<u32>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		if MigrationStatuses::<T>::contains_key(Source::name().as_bytes()) {
+			log::info!(target: "tokens", "migration from {} already started, skipping", Source::name());
+			return T::DbWeight::get().reads(1);
+		}
+
+		log::info!(target: "tokens", "migration from {} started", Source::name());
+		MigrationStatuses::<T>::insert(
+			Source::name().as_bytes(),
+			MigrationStatus::Ongoing { cursor: Vec::new() },
+		);
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		let total_issuance = Source::total_issuance()
+			.into_iter()
+			.map(|(currency_id, _)| (currency_id, Pallet::<T>::total_issuance(currency_id)))
+			.collect::<Vec<_>>();
+		Ok(total_issuance.encode())
+	}
+
+	/// The balances are only migrated in `on_idle`, after `post_upgrade` ran, so
+	/// this only checks that the migration is scheduled and nothing was migrated
+	/// yet.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let total_issuance = <Vec<(T::CurrencyId, T::Balance)>>::decode(&mut &state[..])
+			.map_err(|_| "failed to decode the total issuance")?;
+		ensure!(
+			MigrationStatuses::<T>::contains_key(Source::name().as_bytes()),
+			"migration status should be set"
+		);
+		for (currency_id, before) in total_issuance {
+			ensure!(
+				Pallet::<T>::total_issuance(currency_id) == before,
+				"balances should only be migrated in on_idle"
+			);
+		}
+		Ok(())
+	}
+}
+
+impl<T, Source, ItemsPerBlock> MultiBlockMigration<BlockNumberFor<T>> for MigrateToTokens<T, Source, ItemsPerBlock>
+where
+	T: Config,
+	Source: MigrationSource<T::AccountId, T::CurrencyId, T::Balance>,
+	ItemsPerBlock: Get<u32>,
+{
+	fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+		Self::step(remaining_weight)
+	}
+}
+
+/// `BalanceLock` before locks could expire.
+#[derive(Decode)]
+struct OldBalanceLock<Balance> {
+	id: LockIdentifier,
+	amount: Balance,
+}
+
+/// The storage layout of version 1.
+mod v1 {
+	use super::*;
+
+	/// `BalanceLock` before locks had withdraw reasons.
+	#[derive(Encode, Decode)]
+	pub struct BalanceLock<Balance, BlockNumber> {
+		pub id: LockIdentifier,
+		pub amount: Balance,
+		pub expires_at: Option<BlockNumber>,
+	}
+}
+
+/// Translate the next entries of `Locks` from `Old` to `New`, at most
+/// `items_per_block` within `weight_limit`, and put the next storage version
+/// once all are translated.
+///
+/// The migration named `name` waits until the storage version is `from_version`,
+/// and completes without translating anything if it is already past it.
+fn step_locks<T, Old, New>(
+	name: &[u8],
+	from_version: u16,
+	items_per_block: u32,
+	weight_limit: Weight,
+	translate: impl Fn(Old) -> Option<New>,
+) -> Weight
+where
+	T: Config,
+	Old: Decode,
+	New: Encode,
+{
+	// read the status and the storage version
+	let weight = T::DbWeight::get().reads(2);
+	let cursor = match MigrationStatuses::<T>::get(name) {
+		Some(MigrationStatus::Ongoing { cursor }) => cursor,
+		_ => return T::DbWeight::get().reads(1),
+	};
+	let onchain_version = Pallet::<T>::on_chain_storage_version();
+	if onchain_version < from_version {
+		return weight;
+	}
+	if onchain_version > from_version {
+		MigrationStatuses::<T>::insert(name, MigrationStatus::Completed);
+		return weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	// read and write the locks
+	let weight_per_entry = T::DbWeight::get().reads_writes(1, 1);
+	let limit = weight_limit
+		.saturating_sub(weight.saturating_add(T::DbWeight::get().writes(2)))
+		.checked_div_per_component(&weight_per_entry)
+		// `None` if the weight per entry is zero
+		.unwrap_or(u64::MAX)
+		.min(items_per_block.into()) as u32;
+	if limit == 0 {
+		return weight;
+	}
+
+	let prefix = Locks::<T>::final_prefix().to_vec();
+	let (entries, cursor) = iter_from(prefix.clone(), cursor, limit, |raw_key, mut raw_value| {
+		Ok((raw_key.to_vec(), Old::decode(&mut raw_value)?))
+	});
+	for (raw_key, old_locks) in entries {
+		let key = [&prefix[..], &raw_key[..]].concat();
+		match translate(old_locks) {
+			Some(locks) => unhashed::put(&key, &locks),
+			None => unhashed::kill(&key),
+		}
+	}
+	let weight = weight.saturating_add(weight_per_entry.saturating_mul(limit.into()));
+
+	let status = match cursor {
+		Some(cursor) => MigrationStatus::Ongoing { cursor },
+		None => {
+			StorageVersion::new(from_version.saturating_add(1)).put::<Pallet<T>>();
+			log::info!(target: "tokens", "locks migrated to version {}", from_version.saturating_add(1));
+			MigrationStatus::Completed
+		}
+	};
+	MigrationStatuses::<T>::insert(name, status);
+	weight.saturating_add(T::DbWeight::get().writes(2))
+}
+
+/// Start the migration of `Locks` named `name` unless the storage version is
+/// already `to_version` or the migration was started before.
+fn start_locks_migration<T: Config>(name: &[u8], to_version: u16) -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= to_version || MigrationStatuses::<T>::contains_key(name) {
+		log::info!(target: "tokens", "locks are already migrated to version {}, skipping", to_version);
+		return T::DbWeight::get().reads(2);
+	}
+
+	log::info!(target: "tokens", "migration of locks to version {} started", to_version);
+	MigrationStatuses::<T>::insert(name, MigrationStatus::Ongoing { cursor: Vec::new() });
+	T::DbWeight::get().reads_writes(2, 1)
+}
+
+/// Migrate `Locks` to locks without expiry, at most `ItemsPerBlock` accounts
+/// per block.
+///
+/// Like [`MigrateToTokens`], the migration is started by `on_runtime_upgrade`
+/// and continues in `on_idle`, so it must be added to the runtime migrations
+/// and to the `MultiBlockMigrations` of this pallet. The storage version is
+/// updated once all locks are migrated, until then the locks and freezes
+/// can't be changed.
+pub struct MigrateToV1<T, ItemsPerBlock>(PhantomData<(T, ItemsPerBlock)>);
+
+impl<T: Config, ItemsPerBlock> MigrateToV1<T, ItemsPerBlock> {
+	/// The key of this migration in `MigrationStatuses`.
+	const NAME: &'static [u8] = b"LocksV1";
+}
+
+impl<T: Config, ItemsPerBlock: Get<u32>> OnRuntimeUpgrade for MigrateToV1<T, ItemsPerBlock> {
+	fn on_runtime_upgrade() -> Weight {
+		start_locks_migration::<T>(Self::NAME, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok((Locks::<T>::iter_keys().count() as u64).encode())
+	}
+
+	/// The locks are only migrated in `on_idle`, after `post_upgrade` ran, so
+	/// this only checks that the migration is scheduled if needed.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let count = u64::decode(&mut &state[..]).map_err(|_| "failed to decode the number of locks")?;
+		ensure!(
+			Locks::<T>::iter_keys().count() as u64 == count,
+			"the number of locks changed"
+		);
+		ensure!(
+			Pallet::<T>::on_chain_storage_version() >= 1 || MigrationStatuses::<T>::contains_key(Self::NAME),
+			"migration status should be set"
+		);
+		Ok(())
+	}
+}
+
+impl<T: Config, ItemsPerBlock: Get<u32>> MultiBlockMigration<BlockNumberFor<T>> for MigrateToV1<T, ItemsPerBlock> {
+	fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+		step_locks::<T, Vec<OldBalanceLock<T::Balance>>, _>(
+			Self::NAME,
+			0,
+			ItemsPerBlock::get(),
+			remaining_weight,
+			|old_locks| {
+				let locks = old_locks
+					.into_iter()
+					.map(|lock| v1::BalanceLock::<T::Balance, BlockNumberFor<T>> {
+						id: lock.id,
+						amount: lock.amount,
+						expires_at: None,
+					})
+					.collect::<Vec<_>>();
+				Some(locks)
+			},
+		)
+	}
+}
+
+/// Migrate `Locks` to locks which prevent withdrawals for all reasons, as
+/// they did before, at most `ItemsPerBlock` accounts per block.
+///
+/// Like [`MigrateToV1`], the migration continues in `on_idle`. It waits for
+/// [`MigrateToV1`] to complete first, so both can be added to the same
+/// upgrade, in order.
+pub struct MigrateToV2<T, ItemsPerBlock>(PhantomData<(T, ItemsPerBlock)>);
+
+impl<T: Config, ItemsPerBlock> MigrateToV2<T, ItemsPerBlock> {
+	/// The key of this migration in `MigrationStatuses`.
+	const NAME: &'static [u8] = b"LocksV2";
+}
+
+impl<T: Config, ItemsPerBlock: Get<u32>> OnRuntimeUpgrade for MigrateToV2<T, ItemsPerBlock> {
+	fn on_runtime_upgrade() -> Weight {
+		start_locks_migration::<T>(Self::NAME, 2)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok((Locks::<T>::iter_keys().count() as u64).encode())
+	}
+
+	/// The locks are only migrated in `on_idle`, after `post_upgrade` ran, so
+	/// this only checks that the migration is scheduled if needed.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let count = u64::decode(&mut &state[..]).map_err(|_| "failed to decode the number of locks")?;
+		ensure!(
+			Locks::<T>::iter_keys().count() as u64 == count,
+			"the number of locks changed"
+		);
+		ensure!(
+			Pallet::<T>::on_chain_storage_version() >= 2 || MigrationStatuses::<T>::contains_key(Self::NAME),
+			"migration status should be set"
+		);
+		Ok(())
+	}
+}
+
+impl<T: Config, ItemsPerBlock: Get<u32>> MultiBlockMigration<BlockNumberFor<T>> for MigrateToV2<T, ItemsPerBlock> {
+	fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+		step_locks::<T, Vec<v1::BalanceLock<T::Balance, BlockNumberFor<T>>>, _>(
+			Self::NAME,
+			1,
+			ItemsPerBlock::get(),
+			remaining_weight,
+			|old_locks| {
+				let locks = old_locks
+					.into_iter()
+					.map(|lock| BalanceLock {
+						id: lock.id,
+						amount: lock.amount,
+						expires_at: lock.expires_at,
+						reasons: LockReasons::default(),
+					})
+					.collect::<Vec<_>>();
+				// the number of locks is unchanged, so it can't exceed `MaxLocks`
+				BoundedVec::<_, T::MaxLocks>::try_from(locks).ok()
+			},
+		)
+	}
+}
+
+/// Seed `Holds` with the holds made before they were tracked per reason.
+///
+/// These holds were only added to the reserved balance, so they can't be told
+/// apart from other reserves and are listed by `LegacyHolds` as `(who,
+/// currency_id, reason, amount)`, e.g. from the storage of the pallets which
+/// made them. Each amount is capped by the reserved balance which is neither
+/// named nor held yet. The migration only runs once.
+///
+/// Legacy holds which are not seeded can't be released by reason, only with
+/// `MultiReservableCurrency::unreserve`.
+pub struct MigrateToHolds<T, LegacyHolds>(PhantomData<(T, LegacyHolds)>);
+
+impl<T, LegacyHolds> MigrateToHolds<T, LegacyHolds>
+where
+	T: Config,
+	LegacyHolds: Get<Vec<(T::AccountId, T::CurrencyId, T::RuntimeHoldReason, T::Balance)>>,
+{
+	/// The key of this migration in `MigrationStatuses`.
+	const NAME: &'static [u8] = b"LegacyHolds";
+}
+
+impl<T, LegacyHolds> OnRuntimeUpgrade for MigrateToHolds<T, LegacyHolds>
+where
+	T: Config,
+	LegacyHolds: Get<Vec<(T::AccountId, T::CurrencyId, T::RuntimeHoldReason, T::Balance)>>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		if MigrationStatuses::<T>::contains_key(Self::NAME) {
+			log::info!(target: "tokens", "legacy holds are already migrated, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let legacy_holds = LegacyHolds::get();
+		let count = legacy_holds.len() as u64;
+		for (who, currency_id, reason, amount) in legacy_holds {
+			let reserved = Pallet::<T>::accounts(&who, currency_id).reserved;
+			let holds = Pallet::<T>::holds(&who, currency_id);
+			let accounted = Pallet::<T>::reserves(&who, currency_id)
+				.iter()
+				.map(|reserve| reserve.amount)
+				.chain(holds.iter().map(|hold| hold.amount))
+				.fold(T::Balance::zero(), |acc, amount| acc.saturating_add(amount));
+			let amount = amount.min(reserved.saturating_sub(accounted));
+			if amount.is_zero() {
+				continue;
+			}
+
+			let held = holds
+				.iter()
+				.find(|hold| hold.id == reason)
+				.map_or(Zero::zero(), |hold| hold.amount);
+			if Pallet::<T>::set_hold(currency_id, &who, &reason, held.saturating_add(amount)).is_err() {
+				log::warn!(target: "tokens", "too many holds, skipping a legacy hold");
+			}
+		}
+		MigrationStatuses::<T>::insert(Self::NAME, MigrationStatus::Completed);
+
+		log::info!(target: "tokens", "migrated {} legacy holds", count);
+		// read the account, reserves and holds, write the holds
+		T::DbWeight::get().reads_writes(count.saturating_mul(3).saturating_add(1), count.saturating_add(1))
+	}
+}
@@ -27,6 +27,7 @@ pub type ReserveIdentifier = [u8; 8];
 pub const DOT: CurrencyId = 1;
 pub const BTC: CurrencyId = 2;
 pub const ETH: CurrencyId = 3;
+pub const KSM: CurrencyId = 4;
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const CHARLIE: AccountId = AccountId32::new([2u8; 32]);
@@ -38,6 +39,13 @@ pub const ID_3: LockIdentifier = *b"3       ";
 pub const RID_1: ReserveIdentifier = [1u8; 8];
 pub const RID_2: ReserveIdentifier = [2u8; 8];
 
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub enum TestId {
+	Foo,
+	Bar,
+	Baz,
+}
+
 use crate as tokens;
 
 impl frame_system::Config for Runtime {
@@ -216,12 +224,41 @@ impl Contains<AccountId> for MockDustRemovalWhitelist {
 	}
 }
 
+pub struct MockCheckpointCurrencies;
+impl Contains<CurrencyId> for MockCheckpointCurrencies {
+	fn contains(currency_id: &CurrencyId) -> bool {
+		*currency_id == BTC
+	}
+}
+
+thread_local! {
+	pub static NON_SUFFICIENT_CURRENCY: RefCell<CurrencyId> = RefCell::new(KSM);
+}
+
+pub struct MockSufficientCurrencies;
+impl Contains<CurrencyId> for MockSufficientCurrencies {
+	fn contains(currency_id: &CurrencyId) -> bool {
+		*currency_id != NON_SUFFICIENT_CURRENCY.with(|v| *v.borrow())
+	}
+}
+
+parameter_type_with_key! {
+	pub MaxTotalIssuance: |currency_id: CurrencyId| -> Option<Balance> {
+		#[allow(clippy::match_ref_pats)] // false positive
+		match currency_id {
+			&ETH => Some(1_000),
+			_ => None,
+		}
+	};
+}
+
 parameter_type_with_key! {
 	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
 		#[allow(clippy::match_ref_pats)] // false positive
 		match currency_id {
 			&BTC => 1,
 			&DOT => 2,
+			&KSM => 2,
 			_ => 0,
 		}
 	};
@@ -410,6 +447,14 @@ where
 	type OnKilledTokenAccount = TrackKilledAccounts<T>;
 }
 
+parameter_types! {
+	pub const BalancesPalletName: &'static str = "Balances";
+	pub const GetBtcId: CurrencyId = BTC;
+}
+
+pub type BalancesMigration =
+	migrations::MigrateToTokens<Runtime, migrations::PalletBalances<BalancesPalletName, GetBtcId>, ConstU32<2>>;
+
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = Balance;
@@ -421,7 +466,25 @@ impl Config for Runtime {
 	type MaxLocks = ConstU32<2>;
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = ReserveIdentifier;
+	type RuntimeHoldReason = TestId;
+	type MaxHolds = ConstU32<2>;
+	type FreezeIdentifier = TestId;
+	type MaxFreezes = ConstU32<2>;
 	type DustRemovalWhitelist = MockDustRemovalWhitelist;
+	type MaxTotalIssuance = MaxTotalIssuance;
+	type ApprovalDeposits = ExistentialDeposits;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxBatchTransfers = ConstU32<3>;
+	type MultiBlockMigrations = BalancesMigration;
+	type MaxReapAccounts = ConstU32<3>;
+	type CheckpointCurrencies = MockCheckpointCurrencies;
+	type MaxCheckpoints = ConstU32<3>;
+	type IsSufficient = MockSufficientCurrencies;
+	type ComplianceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransferAllCurrencies = ConstU32<2>;
+	type MaxMemoLength = ConstU32<8>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type GetBenchmarkCurrencyId = ConstU32<DOT>;
 }
 pub type TreasuryCurrencyAdapter = <Runtime as pallet_treasury::Config>::Currency;
 
@@ -439,6 +502,8 @@ construct_runtime!(
 #[derive(Default)]
 pub struct ExtBuilder {
 	balances: Vec<(AccountId, CurrencyId, Balance)>,
+	locks: Vec<(AccountId, CurrencyId, LockIdentifier, Balance)>,
+	reserves: Vec<(AccountId, CurrencyId, ReserveIdentifier, Balance)>,
 	treasury_genesis: bool,
 }
 
@@ -448,6 +513,16 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn locks(mut self, mut locks: Vec<(AccountId, CurrencyId, LockIdentifier, Balance)>) -> Self {
+		self.locks.append(&mut locks);
+		self
+	}
+
+	pub fn reserves(mut self, mut reserves: Vec<(AccountId, CurrencyId, ReserveIdentifier, Balance)>) -> Self {
+		self.reserves.append(&mut reserves);
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::<Runtime>::default()
 			.build_storage()
@@ -455,6 +530,8 @@ impl ExtBuilder {
 
 		tokens::GenesisConfig::<Runtime> {
 			balances: self.balances,
+			locks: self.locks,
+			reserves: self.reserves,
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
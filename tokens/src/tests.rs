@@ -3,10 +3,13 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{ConstU32, Imbalance},
+};
 use frame_system::RawOrigin;
 use mock::*;
-use sp_runtime::{traits::BadOrigin, TokenError};
+use sp_runtime::{traits::BadOrigin, ArithmeticError, TokenError};
 
 // *************************************************
 // tests for genesis
@@ -25,6 +28,49 @@ fn genesis_issuance_should_work() {
 		});
 }
 
+#[test]
+fn genesis_locks_and_reserves_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.locks(vec![(ALICE, DOT, ID_1, 30), (ALICE, DOT, ID_2, 50)])
+		.reserves(vec![(BOB, DOT, RID_1, 20), (BOB, DOT, RID_2, 30)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 50);
+			assert_eq!(Tokens::locks(&ALICE, DOT).len(), 2);
+			assert_eq!(System::consumers(&ALICE), 1);
+
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 100);
+			assert_eq!(Tokens::reserved_balance(DOT, &BOB), 50);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 20);
+			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &BOB), 30);
+
+			assert_eq!(Tokens::total_issuance(DOT), 250);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+#[should_panic(expected = "duplicate locks in genesis.")]
+fn genesis_duplicate_locks_should_fail() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.locks(vec![(ALICE, DOT, ID_1, 30), (ALICE, DOT, ID_1, 50)])
+		.build();
+}
+
+#[test]
+#[should_panic(expected = "locks can only be set on endowed accounts in genesis.")]
+fn genesis_locks_on_unendowed_account_should_fail() {
+	ExtBuilder::default().locks(vec![(ALICE, DOT, ID_1, 30)]).build();
+}
+
+#[test]
+#[should_panic(expected = "named reserves can only be set on endowed accounts in genesis.")]
+fn genesis_reserves_on_unendowed_account_should_fail() {
+	ExtBuilder::default().reserves(vec![(ALICE, DOT, RID_1, 10)]).build();
+}
+
 // *************************************************
 // tests for call
 // *************************************************
@@ -264,6 +310,916 @@ fn set_balance_should_work() {
 		});
 }
 
+#[test]
+fn approve_transfer_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), DOT, BOB, 50));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ApprovedTransfer {
+				currency_id: DOT,
+				owner: ALICE,
+				delegate: BOB,
+				amount: 50,
+			}));
+			assert_eq!(
+				Tokens::approvals((ALICE, DOT, BOB)),
+				Some(Approval { amount: 50, deposit: 2 })
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 98);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 2);
+
+			// approving again adds up and doesn't take another deposit
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), DOT, BOB, 20));
+			assert_eq!(
+				Tokens::approvals((ALICE, DOT, BOB)),
+				Some(Approval { amount: 70, deposit: 2 })
+			);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 2);
+
+			// cannot pay the deposit
+			assert_noop!(
+				Tokens::approve_transfer(Some(CHARLIE).into(), DOT, BOB, 50),
+				Error::<Runtime>::BalanceTooLow
+			);
+		});
+}
+
+#[test]
+fn cancel_approval_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::cancel_approval(Some(ALICE).into(), DOT, BOB),
+				Error::<Runtime>::Unapproved
+			);
+
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), DOT, BOB, 50));
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 2);
+
+			assert_ok!(Tokens::cancel_approval(Some(ALICE).into(), DOT, BOB));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ApprovalCancelled {
+				currency_id: DOT,
+				owner: ALICE,
+				delegate: BOB,
+			}));
+			assert_eq!(Tokens::approvals((ALICE, DOT, BOB)), None);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		});
+}
+
+#[test]
+fn transfer_approved_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::transfer_approved(Some(BOB).into(), ALICE, DOT, CHARLIE, 10),
+				Error::<Runtime>::Unapproved
+			);
+
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), DOT, BOB, 50));
+
+			// exceed the approved amount
+			assert_noop!(
+				Tokens::transfer_approved(Some(BOB).into(), ALICE, DOT, CHARLIE, 51),
+				Error::<Runtime>::Unapproved
+			);
+			// only the delegate can use the approval
+			assert_noop!(
+				Tokens::transfer_approved(Some(CHARLIE).into(), ALICE, DOT, CHARLIE, 10),
+				Error::<Runtime>::Unapproved
+			);
+
+			assert_ok!(Tokens::transfer_approved(Some(BOB).into(), ALICE, DOT, CHARLIE, 30));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: DOT,
+				from: ALICE,
+				to: CHARLIE,
+				amount: 30,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::TransferredApproved {
+				currency_id: DOT,
+				owner: ALICE,
+				delegate: BOB,
+				destination: CHARLIE,
+				amount: 30,
+			}));
+			assert_eq!(
+				Tokens::approvals((ALICE, DOT, BOB)),
+				Some(Approval { amount: 20, deposit: 2 })
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 68);
+			assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 30);
+
+			// using up the approval returns the deposit
+			assert_ok!(Tokens::transfer_approved(Some(BOB).into(), ALICE, DOT, CHARLIE, 20));
+			assert_eq!(Tokens::approvals((ALICE, DOT, BOB)), None);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 50);
+		});
+}
+
+#[test]
+fn freeze_currency_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::freeze_currency(Some(ALICE).into(), DOT), BadOrigin);
+			assert_noop!(
+				Tokens::thaw_currency(RawOrigin::Root.into(), DOT),
+				Error::<Runtime>::NotFrozen
+			);
+
+			assert_ok!(Tokens::freeze_currency(RawOrigin::Root.into(), DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::CurrencyFrozen { currency_id: DOT }));
+			assert!(FrozenCurrencies::<Runtime>::contains_key(DOT));
+			assert_noop!(
+				Tokens::freeze_currency(RawOrigin::Root.into(), DOT),
+				Error::<Runtime>::AlreadyFrozen
+			);
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Error::<Runtime>::CurrencyFrozen
+			);
+			assert_noop!(
+				Tokens::transfer(Some(BOB).into(), ALICE, DOT, 10),
+				Error::<Runtime>::CurrencyFrozen
+			);
+			assert_noop!(Tokens::withdraw(DOT, &ALICE, 10), Error::<Runtime>::CurrencyFrozen);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::reducible_balance(
+					DOT,
+					&ALICE,
+					Preservation::Expendable,
+					Fortitude::Polite
+				),
+				0
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_withdraw(DOT, &ALICE, 10),
+				WithdrawConsequence::Frozen
+			);
+			// other currencies are not affected
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+
+			assert_ok!(Tokens::thaw_currency(RawOrigin::Root.into(), DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::CurrencyThawed { currency_id: DOT }));
+			assert!(!FrozenCurrencies::<Runtime>::contains_key(DOT));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+		});
+}
+
+#[test]
+fn freeze_account_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::freeze_account(Some(ALICE).into(), ALICE, DOT), BadOrigin);
+			assert_noop!(
+				Tokens::thaw_account(RawOrigin::Root.into(), ALICE, DOT),
+				Error::<Runtime>::NotFrozen
+			);
+
+			assert_ok!(Tokens::freeze_account(RawOrigin::Root.into(), ALICE, DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::AccountFrozen {
+				currency_id: DOT,
+				who: ALICE,
+			}));
+			assert!(FrozenAccounts::<Runtime>::contains_key(ALICE, DOT));
+			assert_noop!(
+				Tokens::freeze_account(RawOrigin::Root.into(), ALICE, DOT),
+				Error::<Runtime>::AlreadyFrozen
+			);
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::burn_from(DOT, &ALICE, 10, Precision::Exact, Fortitude::Polite),
+				TokenError::Frozen
+			);
+			assert_noop!(Tokens::withdraw(DOT, &ALICE, 10), Error::<Runtime>::AccountFrozen);
+			// frozen account can still receive
+			assert_ok!(Tokens::transfer(Some(BOB).into(), ALICE, DOT, 10));
+			// other currencies are not affected
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+
+			assert_ok!(Tokens::thaw_account(RawOrigin::Root.into(), ALICE, DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::AccountThawed {
+				currency_id: DOT,
+				who: ALICE,
+			}));
+			assert!(!FrozenAccounts::<Runtime>::contains_key(ALICE, DOT));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+		});
+}
+
+#[test]
+fn frozen_account_can_not_move_reserved_balance() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 20));
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::hold(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				20
+			));
+			assert_ok!(Tokens::freeze_account(RawOrigin::Root.into(), ALICE, DOT));
+
+			assert!(!Tokens::can_reserve(DOT, &ALICE, 10));
+			assert_noop!(Tokens::reserve(DOT, &ALICE, 10), Error::<Runtime>::AccountFrozen);
+			assert!(!<Tokens as fungibles::InspectHold<_>>::can_hold(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				10
+			));
+			assert_noop!(
+				<Tokens as fungibles::MutateHold<_>>::hold(DOT, &TestId::Foo, &ALICE, 10),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				<Tokens as fungibles::MutateHold<_>>::transfer_on_hold(
+					DOT,
+					&TestId::Foo,
+					&ALICE,
+					&BOB,
+					20,
+					Precision::Exact,
+					Restriction::Free,
+					Fortitude::Polite
+				),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 20, BalanceStatus::Free),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &ALICE, &BOB, 20, BalanceStatus::Free),
+				Error::<Runtime>::AccountFrozen
+			);
+
+			assert_ok!(Tokens::thaw_account(RawOrigin::Root.into(), ALICE, DOT));
+			assert_ok!(Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 10, BalanceStatus::Free));
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 110);
+		});
+}
+
+#[test]
+fn compliance_members_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(Tokens::add_compliance_member(Some(ALICE).into(), DOT, ALICE), BadOrigin);
+		assert_noop!(
+			Tokens::remove_compliance_member(RawOrigin::Root.into(), DOT, ALICE),
+			Error::<Runtime>::NotComplianceMember
+		);
+
+		assert_ok!(Tokens::add_compliance_member(RawOrigin::Root.into(), DOT, ALICE));
+		System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ComplianceMemberAdded {
+			currency_id: DOT,
+			who: ALICE,
+		}));
+		assert!(ComplianceMembers::<Runtime>::contains_key(DOT, ALICE));
+		assert_noop!(
+			Tokens::add_compliance_member(RawOrigin::Root.into(), DOT, ALICE),
+			Error::<Runtime>::AlreadyComplianceMember
+		);
+
+		assert_ok!(Tokens::remove_compliance_member(RawOrigin::Root.into(), DOT, ALICE));
+		System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ComplianceMemberRemoved {
+			currency_id: DOT,
+			who: ALICE,
+		}));
+		assert!(!ComplianceMembers::<Runtime>::contains_key(DOT, ALICE));
+	});
+}
+
+#[test]
+fn compliance_policy_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Tokens::compliance_policies(DOT), CompliancePolicy::Open);
+			assert_noop!(
+				Tokens::set_compliance_policy(Some(ALICE).into(), DOT, CompliancePolicy::Allowlist),
+				BadOrigin
+			);
+
+			// only members may hold and move the currency
+			assert_ok!(Tokens::set_compliance_policy(
+				RawOrigin::Root.into(),
+				DOT,
+				CompliancePolicy::Allowlist
+			));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::CompliancePolicySet {
+				currency_id: DOT,
+				policy: CompliancePolicy::Allowlist,
+			}));
+			assert_ok!(Tokens::add_compliance_member(RawOrigin::Root.into(), DOT, ALICE));
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Error::<Runtime>::NotCompliant
+			);
+			assert_noop!(Tokens::deposit(DOT, &BOB, 10), Error::<Runtime>::NotCompliant);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(DOT, &BOB, 10, Provenance::Minted),
+				DepositConsequence::Blocked
+			);
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 20));
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 10, BalanceStatus::Free),
+				Error::<Runtime>::NotCompliant
+			);
+			// other currencies are not affected
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+
+			assert_ok!(Tokens::add_compliance_member(RawOrigin::Root.into(), DOT, BOB));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+			assert_ok!(Tokens::deposit(DOT, &BOB, 10));
+			assert_ok!(Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 10, BalanceStatus::Free));
+
+			// members may not hold and move the currency
+			assert_ok!(Tokens::set_compliance_policy(
+				RawOrigin::Root.into(),
+				DOT,
+				CompliancePolicy::Denylist
+			));
+			assert_ok!(Tokens::remove_compliance_member(RawOrigin::Root.into(), DOT, ALICE));
+			assert_noop!(
+				Tokens::transfer(Some(BOB).into(), ALICE, DOT, 10),
+				Error::<Runtime>::NotCompliant
+			);
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Error::<Runtime>::NotCompliant
+			);
+			assert_noop!(Tokens::deposit(DOT, &BOB, 10), Error::<Runtime>::NotCompliant);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 10));
+		});
+}
+
+#[test]
+fn force_unreserve_named_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 50));
+			assert_noop!(
+				Tokens::force_unreserve_named(Some(ALICE).into(), ALICE, DOT, RID_1, 20),
+				BadOrigin
+			);
+			assert_noop!(
+				Tokens::force_unreserve_named(RawOrigin::Root.into(), ALICE, DOT, RID_2, 20),
+				Error::<Runtime>::NamedReserveNotFound
+			);
+
+			assert_ok!(Tokens::force_unreserve_named(
+				RawOrigin::Root.into(),
+				ALICE,
+				DOT,
+				RID_1,
+				20
+			));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::UnreservedNamed {
+				id: RID_1,
+				currency_id: DOT,
+				who: ALICE,
+				amount: 20,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 70);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
+		});
+}
+
+#[test]
+fn force_repatriate_reserved_named_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 50));
+			assert_noop!(
+				Tokens::force_repatriate_reserved_named(
+					Some(ALICE).into(),
+					ALICE,
+					BOB,
+					DOT,
+					RID_1,
+					20,
+					BalanceStatus::Free
+				),
+				BadOrigin
+			);
+			assert_noop!(
+				Tokens::force_repatriate_reserved_named(
+					RawOrigin::Root.into(),
+					ALICE,
+					BOB,
+					DOT,
+					RID_2,
+					20,
+					BalanceStatus::Free
+				),
+				Error::<Runtime>::NamedReserveNotFound
+			);
+
+			assert_ok!(Tokens::force_repatriate_reserved_named(
+				RawOrigin::Root.into(),
+				ALICE,
+				BOB,
+				DOT,
+				RID_1,
+				20,
+				BalanceStatus::Reserved
+			));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriatedNamed {
+				id: RID_1,
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 20,
+				status: BalanceStatus::Reserved,
+			}));
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 20);
+
+			assert_ok!(Tokens::force_repatriate_reserved_named(
+				RawOrigin::Root.into(),
+				ALICE,
+				BOB,
+				DOT,
+				RID_1,
+				30,
+				BalanceStatus::Free
+			));
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 130);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn transfer_batch_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::transfer_batch(
+					Some(ALICE).into(),
+					vec![(BOB, DOT, 50), (CHARLIE, BTC, 101)].try_into().unwrap()
+				),
+				Error::<Runtime>::BalanceTooLow
+			);
+
+			assert_ok!(Tokens::transfer_batch(
+				Some(ALICE).into(),
+				vec![(BOB, DOT, 50), (CHARLIE, BTC, 30), (BOB, BTC, 20)]
+					.try_into()
+					.unwrap()
+			));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 50,
+			}));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: BTC,
+				from: ALICE,
+				to: CHARLIE,
+				amount: 30,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::BatchTransferred {
+				from: ALICE,
+				count: 3,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 50);
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 20);
+			assert_eq!(Tokens::free_balance(BTC, &CHARLIE), 30);
+		});
+}
+
+#[test]
+fn account_currencies_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 200), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(BTC, &ALICE, 50));
+			let mut currencies = Tokens::account_currencies(&ALICE);
+			currencies.sort_by_key(|(currency_id, _)| *currency_id);
+			assert_eq!(
+				currencies,
+				vec![
+					(
+						DOT,
+						AccountData {
+							free: 100,
+							reserved: 0,
+							frozen: 0
+						}
+					),
+					(
+						BTC,
+						AccountData {
+							free: 150,
+							reserved: 50,
+							frozen: 0
+						}
+					),
+				]
+			);
+			assert_eq!(Tokens::account_currencies(&CHARLIE), vec![]);
+		});
+}
+
+#[test]
+fn max_total_issuance_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Tokens::deposit(ETH, &ALICE, 600));
+		assert_noop!(
+			Tokens::deposit(ETH, &BOB, 401),
+			Error::<Runtime>::MaxTotalIssuanceExceeded
+		);
+
+		assert_eq!(
+			<Tokens as fungibles::Inspect<_>>::can_deposit(ETH, &BOB, 400, Provenance::Minted),
+			DepositConsequence::Success
+		);
+		assert_eq!(
+			<Tokens as fungibles::Inspect<_>>::can_deposit(ETH, &BOB, 401, Provenance::Minted),
+			DepositConsequence::Overflow
+		);
+		assert_noop!(
+			<Tokens as fungibles::Mutate<_>>::mint_into(ETH, &BOB, 401),
+			ArithmeticError::Overflow
+		);
+		assert_ok!(<Tokens as fungibles::Mutate<_>>::mint_into(ETH, &BOB, 400));
+		assert_eq!(Tokens::total_issuance(ETH), 1_000);
+
+		<Tokens as fungibles::Unbalanced<_>>::set_total_issuance(ETH, 2_000);
+		assert_eq!(Tokens::total_issuance(ETH), 1_000);
+
+		assert_ok!(Tokens::withdraw(ETH, &ALICE, 500));
+		assert_eq!(Tokens::total_issuance(ETH), 500);
+		let imbalance = <CurrencyAdapter<Runtime, ConstU32<ETH>> as PalletCurrency<AccountId>>::issue(600);
+		assert_eq!(imbalance.peek(), 500);
+		assert_eq!(Tokens::total_issuance(ETH), 1_000);
+
+		// the cap applies to deposits which change the total issuance through the
+		// imbalance too
+		let imbalance = <Tokens as orml_traits::MultiCurrencyImbalance<_>>::deposit_creating(ETH, &BOB, 1);
+		assert_eq!(imbalance.peek(), 0);
+		assert_eq!(Tokens::free_balance(ETH, &BOB), 400);
+		let imbalance =
+			<CurrencyAdapter<Runtime, ConstU32<ETH>> as PalletCurrency<AccountId>>::deposit_creating(&BOB, 1);
+		assert_eq!(imbalance.peek(), 0);
+		assert!(
+			<CurrencyAdapter<Runtime, ConstU32<ETH>> as PalletCurrency<AccountId>>::deposit_into_existing(&BOB, 1)
+				.is_err()
+		);
+		assert_eq!(Tokens::total_issuance(ETH), 1_000);
+
+		// uncapped currency
+		assert_ok!(Tokens::deposit(DOT, &ALICE, Balance::max_value()));
+	});
+}
+
+#[test]
+fn try_state_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve_named(&RID_1, BTC, &ALICE, 50));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, BTC, 20));
+			assert_ok!(Tokens::do_try_state());
+
+			TotalIssuance::<Runtime>::insert(DOT, 201);
+			assert!(Tokens::do_try_state().is_err());
+			TotalIssuance::<Runtime>::insert(DOT, 200);
+
+			Accounts::<Runtime>::mutate(ALICE, DOT, |account| account.frozen = 20);
+			assert!(Tokens::do_try_state().is_err());
+			Accounts::<Runtime>::mutate(ALICE, DOT, |account| account.frozen = 30);
+
+			Accounts::<Runtime>::mutate(ALICE, BTC, |account| {
+				account.free += 10;
+				account.reserved -= 10;
+			});
+			assert!(Tokens::do_try_state().is_err());
+			Accounts::<Runtime>::mutate(ALICE, BTC, |account| {
+				account.free -= 10;
+				account.reserved += 10;
+			});
+
+			// below ED
+			Accounts::<Runtime>::insert(
+				CHARLIE,
+				DOT,
+				AccountData {
+					free: 1,
+					..Default::default()
+				},
+			);
+			TotalIssuance::<Runtime>::insert(DOT, 201);
+			assert!(Tokens::do_try_state().is_err());
+			Accounts::<Runtime>::remove(CHARLIE, DOT);
+
+			// whitelisted account can be below ED
+			Accounts::<Runtime>::insert(
+				DAVE,
+				DOT,
+				AccountData {
+					free: 1,
+					..Default::default()
+				},
+			);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn transfer_all_currencies_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, ETH, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+			assert_ok!(Tokens::set_compliance_policy(
+				RawOrigin::Root.into(),
+				ETH,
+				CompliancePolicy::Allowlist
+			));
+			assert_ok!(Tokens::add_compliance_member(RawOrigin::Root.into(), ETH, ALICE));
+
+			let post_info = Tokens::transfer_all_currencies(Some(ALICE).into(), BOB, true, None).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<Runtime as Config>::WeightInfo::transfer_all_currencies(2))
+			);
+
+			// the reserved balance is left behind
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::CurrencySwept {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 70,
+				result: Ok(()),
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 70);
+
+			// a failed transfer doesn't affect the others
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::CurrencySwept {
+				currency_id: ETH,
+				from: ALICE,
+				to: BOB,
+				amount: 20,
+				result: Err(Error::<Runtime>::NotCompliant.into()),
+			}));
+			assert_eq!(Tokens::free_balance(ETH, &ALICE), 20);
+			assert_eq!(Tokens::free_balance(ETH, &BOB), 0);
+		});
+}
+
+#[test]
+fn transfer_all_currencies_should_respect_limit() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100), (ALICE, ETH, 100)])
+		.build()
+		.execute_with(|| {
+			let post_info = Tokens::transfer_all_currencies(Some(ALICE).into(), BOB, false, None).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<Runtime as Config>::WeightInfo::transfer_all_currencies(2))
+			);
+			assert_eq!(Accounts::<Runtime>::iter_prefix(&ALICE).count(), 1);
+
+			let post_info = Tokens::transfer_all_currencies(Some(ALICE).into(), BOB, false, None).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<Runtime as Config>::WeightInfo::transfer_all_currencies(1))
+			);
+
+			// the zero existential deposit of ETH keeps the emptied account.
+			for currency_id in [DOT, BTC, ETH] {
+				assert_eq!(Tokens::free_balance(currency_id, &ALICE), 0);
+				assert_eq!(Tokens::free_balance(currency_id, &BOB), 100);
+			}
+		});
+}
+
+#[test]
+fn transfer_all_currencies_should_sweep_listed_currencies() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100), (ALICE, ETH, 100)])
+		.build()
+		.execute_with(|| {
+			// DOT and BTC come first in storage order and can't be swept
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 100));
+			assert_ok!(Tokens::reserve(BTC, &ALICE, 100));
+			let post_info = Tokens::transfer_all_currencies(Some(ALICE).into(), BOB, false, None).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<Runtime as Config>::WeightInfo::transfer_all_currencies(2))
+			);
+			assert_eq!(Tokens::free_balance(ETH, &BOB), 0);
+
+			// so ETH has to be listed
+			let post_info =
+				Tokens::transfer_all_currencies(Some(ALICE).into(), BOB, false, Some(vec![ETH].try_into().unwrap()))
+					.unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<Runtime as Config>::WeightInfo::transfer_all_currencies(1))
+			);
+			assert_eq!(Tokens::free_balance(ETH, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(ETH, &BOB), 100);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 100);
+			assert_eq!(Tokens::reserved_balance(BTC, &ALICE), 100);
+		});
+}
+
+#[test]
+fn transfer_with_memo_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			let memo: BoundedVec<u8, <Runtime as Config>::MaxMemoLength> = b"inv-42".to_vec().try_into().unwrap();
+			assert_ok!(Tokens::transfer_with_memo(
+				Some(ALICE).into(),
+				BOB,
+				DOT,
+				50,
+				memo.clone()
+			));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 50,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::TransferMemo {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 50,
+				memo,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 50);
+
+			assert_noop!(
+				Tokens::transfer_with_memo(Some(ALICE).into(), BOB, DOT, 60, Default::default()),
+				Error::<Runtime>::BalanceTooLow
+			);
+			let too_long: Result<BoundedVec<u8, <Runtime as Config>::MaxMemoLength>, _> = vec![0u8; 9].try_into();
+			assert!(too_long.is_err());
+		});
+}
+
+#[test]
+fn reap_dust_accounts_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// accounts left below a raised ED
+			for who in [BOB, CHARLIE, DAVE] {
+				Accounts::<Runtime>::insert(
+					&who,
+					DOT,
+					AccountData {
+						free: 1,
+						..Default::default()
+					},
+				);
+				System::inc_providers(&who);
+			}
+			TotalIssuance::<Runtime>::insert(DOT, 103);
+
+			// DAVE is whitelisted and ALICE is above ED
+			let post_info =
+				Tokens::reap_dust_accounts(Some(ALICE).into(), DOT, vec![BOB, DAVE, ALICE].try_into().unwrap())
+					.unwrap();
+			assert_eq!(post_info.pays_fee, Pays::Yes);
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::DustLost {
+				currency_id: DOT,
+				who: BOB,
+				amount: 1,
+			}));
+			assert!(!Accounts::<Runtime>::contains_key(BOB, DOT));
+			assert_eq!(Tokens::free_balance(DOT, &DAVE), 1);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 1);
+
+			// refund if all accounts were reaped
+			let post_info =
+				Tokens::reap_dust_accounts(Some(ALICE).into(), DOT, vec![CHARLIE].try_into().unwrap()).unwrap();
+			assert_eq!(post_info.pays_fee, Pays::No);
+			assert!(!Accounts::<Runtime>::contains_key(CHARLIE, DOT));
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 2);
+
+			// already reaped
+			let post_info =
+				Tokens::reap_dust_accounts(Some(ALICE).into(), DOT, vec![CHARLIE].try_into().unwrap()).unwrap();
+			assert_eq!(post_info.pays_fee, Pays::Yes);
+			assert_eq!(Tokens::total_issuance(DOT), 103);
+		});
+}
+
+#[test]
+fn balance_checkpoints_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, BTC, 100), (ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+			System::set_block_number(3);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 20));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 20));
+
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 0), Some(100));
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 1), Some(90));
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 2), Some(90));
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 3), Some(70));
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 10), Some(70));
+			// no account yet
+			assert_eq!(Tokens::balance_at(&BOB, BTC, 0), None);
+			assert_eq!(Tokens::balance_at(&BOB, BTC, 1), Some(10));
+			assert_eq!(Tokens::balance_at(&CHARLIE, BTC, 1), None);
+			// not checkpointed
+			assert_eq!(Tokens::balance_at(&ALICE, DOT, 1), None);
+			assert_eq!(Tokens::total_issuance_at(DOT, 1), None);
+			assert!(BalanceCheckpoints::<Runtime>::get(ALICE, DOT).is_empty());
+
+			// no changes of total issuance yet
+			assert_eq!(Tokens::total_issuance_at(BTC, 1), Some(100));
+
+			System::set_block_number(5);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+			assert_ok!(Tokens::deposit(BTC, &ALICE, 50));
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 5), Some(110));
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 4), Some(70));
+			// the oldest checkpoint is removed
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 0), None);
+			assert_eq!(Tokens::balance_at(&ALICE, BTC, 1), Some(90));
+
+			assert_eq!(Tokens::total_issuance_at(BTC, 4), Some(100));
+			assert_eq!(Tokens::total_issuance_at(BTC, 5), Some(150));
+
+			// the history is removed with the account
+			assert_ok!(Tokens::transfer_all(Some(BOB).into(), ALICE, BTC, false));
+			assert!(!Accounts::<Runtime>::contains_key(BOB, BTC));
+			assert!(BalanceCheckpoints::<Runtime>::get(BOB, BTC).is_empty());
+			assert_eq!(Tokens::balance_at(&BOB, BTC, 5), None);
+		});
+}
+
+#[test]
+fn balance_checkpoints_should_start_at_first_change() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Tokens::checkpoint_starts(BTC), None);
+		assert_eq!(Tokens::balance_at(&ALICE, BTC, 1), None);
+		assert_eq!(Tokens::total_issuance_at(BTC, 1), None);
+
+		System::set_block_number(3);
+		assert_ok!(Tokens::deposit(BTC, &ALICE, 100));
+		assert_eq!(Tokens::checkpoint_starts(BTC), Some(3));
+
+		// unknown before the history started
+		assert_eq!(Tokens::balance_at(&ALICE, BTC, 2), None);
+		assert_eq!(Tokens::total_issuance_at(BTC, 2), None);
+		assert_eq!(Tokens::balance_at(&ALICE, BTC, 3), Some(100));
+		assert_eq!(Tokens::total_issuance_at(BTC, 3), Some(100));
+		assert_eq!(Tokens::balance_at(&BOB, BTC, 3), None);
+	});
+}
+
 // *************************************************
 // tests for inline impl
 // *************************************************
@@ -1134,6 +2090,115 @@ fn account_survive_due_to_dust_transfer_failure() {
 	});
 }
 
+#[test]
+fn non_sufficient_currency_should_take_consumer_reference() {
+	ExtBuilder::default()
+		.balances(vec![
+			(ALICE, DOT, 100),
+			(BOB, DOT, 100),
+			(DustReceiver::get(), DOT, 100),
+		])
+		.build()
+		.execute_with(|| {
+			// can't create an account on its own
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(KSM, &CHARLIE, 10, Provenance::Minted),
+				DepositConsequence::CannotCreate
+			);
+			assert_noop!(Tokens::deposit(KSM, &CHARLIE, 10), TokenError::CannotCreate);
+
+			assert_ok!(Tokens::deposit(KSM, &ALICE, 10));
+			assert_eq!(System::providers(&ALICE), 1);
+			assert_eq!(System::consumers(&ALICE), 1);
+			assert_noop!(
+				<Tokens as MultiCurrency<_>>::transfer(KSM, &ALICE, &CHARLIE, 5),
+				TokenError::CannotCreate
+			);
+
+			// the sufficient account keeps the non-sufficient one alive
+			assert_noop!(
+				Tokens::do_transfer(DOT, &ALICE, &BOB, 100, ExistenceRequirement::AllowDeath),
+				Error::<Runtime>::KeepAlive
+			);
+
+			// the dust is removed and the consumer reference released
+			assert_ok!(<Tokens as MultiCurrency<_>>::transfer(KSM, &ALICE, &BOB, 9));
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, KSM));
+			assert_eq!(Tokens::free_balance(KSM, &DustReceiver::get()), 1);
+			assert_eq!(System::consumers(&ALICE), 0);
+			assert_eq!(System::consumers(&BOB), 1);
+
+			assert_ok!(Tokens::do_transfer(
+				DOT,
+				&ALICE,
+				&BOB,
+				100,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(System::providers(&ALICE), 0);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn repatriate_reserved_of_non_sufficient_currency_should_not_create_account() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::deposit(KSM, &ALICE, 50));
+			assert_ok!(Tokens::reserve(KSM, &ALICE, 10));
+			assert_ok!(Tokens::reserve_named(&RID_1, KSM, &ALICE, 10));
+
+			assert_noop!(
+				Tokens::repatriate_reserved(KSM, &ALICE, &CHARLIE, 10, BalanceStatus::Free),
+				TokenError::CannotCreate
+			);
+			assert_noop!(
+				Tokens::repatriate_reserved_named(&RID_1, KSM, &ALICE, &CHARLIE, 10, BalanceStatus::Reserved),
+				TokenError::CannotCreate
+			);
+
+			// the account can be created once something else keeps it alive
+			assert_ok!(Tokens::deposit(DOT, &CHARLIE, 10));
+			assert_ok!(Tokens::repatriate_reserved(
+				KSM,
+				&ALICE,
+				&CHARLIE,
+				10,
+				BalanceStatus::Free
+			));
+			assert_eq!(Tokens::free_balance(KSM, &CHARLIE), 10);
+			assert_eq!(System::consumers(&CHARLIE), 1);
+		});
+}
+
+#[test]
+fn changing_sufficiency_should_release_the_reference_taken() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::deposit(KSM, &ALICE, 10));
+			assert_eq!(System::providers(&ALICE), 1);
+			assert_eq!(System::consumers(&ALICE), 1);
+
+			// KSM becomes sufficient and DOT non-sufficient
+			NON_SUFFICIENT_CURRENCY.with(|v| *v.borrow_mut() = DOT);
+			assert_ok!(Tokens::withdraw(KSM, &ALICE, 10));
+			assert!(!ConsumerAccounts::<Runtime>::contains_key(ALICE, KSM));
+			assert_eq!(System::providers(&ALICE), 1);
+			assert_eq!(System::consumers(&ALICE), 0);
+
+			assert_ok!(Tokens::deposit(BTC, &ALICE, 10));
+			assert_eq!(System::providers(&ALICE), 2);
+			assert_ok!(Tokens::withdraw(DOT, &ALICE, 100));
+			assert_eq!(System::providers(&ALICE), 1);
+			assert_eq!(System::consumers(&ALICE), 0);
+			NON_SUFFICIENT_CURRENCY.with(|v| *v.borrow_mut() = KSM);
+		});
+}
+
 #[test]
 fn exceeding_max_reserves_should_fail() {
 	ExtBuilder::default()
@@ -346,9 +346,18 @@ fn currency_adapter_combination_locking_should_work() {
 		.balances(vec![(TREASURY_ACCOUNT, DOT, 100)])
 		.build()
 		.execute_with(|| {
-			// withdrawReasons not work
+			// a lock without reasons is removed, as in pallet_balances
 			TreasuryCurrencyAdapter::set_lock(ID_1, &TREASURY_ACCOUNT, u64::max_value(), WithdrawReasons::empty());
 			TreasuryCurrencyAdapter::set_lock(ID_2, &TREASURY_ACCOUNT, 0, WithdrawReasons::all());
+			assert_eq!(Tokens::locks(&TREASURY_ACCOUNT, DOT).len(), 0);
+			assert_ok!(TreasuryCurrencyAdapter::transfer(
+				&TREASURY_ACCOUNT,
+				&ALICE,
+				2,
+				ExistenceRequirement::AllowDeath
+			));
+
+			TreasuryCurrencyAdapter::set_lock(ID_1, &TREASURY_ACCOUNT, u64::max_value(), WithdrawReasons::TRANSFER);
 			assert_noop!(
 				TreasuryCurrencyAdapter::transfer(&TREASURY_ACCOUNT, &ALICE, 2, ExistenceRequirement::AllowDeath),
 				Error::<Runtime>::LiquidityRestrictions
@@ -6,7 +6,7 @@ use super::*;
 use frame_support::assert_ok;
 use mock::*;
 
-const REASON: &() = &();
+const REASON: &TestId = &TestId::Foo;
 
 fn events() -> Vec<RuntimeEvent> {
 	let evt = System::events().into_iter().map(|evt| evt.event).collect::<Vec<_>>();
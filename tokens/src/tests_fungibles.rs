@@ -7,7 +7,7 @@ use frame_support::{assert_noop, assert_ok};
 use mock::*;
 use sp_runtime::{ArithmeticError, TokenError};
 
-const REASON: &() = &();
+const REASON: &TestId = &TestId::Foo;
 
 #[test]
 fn fungibles_inspect_trait_should_work() {
@@ -552,6 +552,403 @@ fn fungibles_mutate_hold_trait_should_work() {
 		});
 }
 
+#[test]
+fn fungibles_hold_reasons_should_be_isolated() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::hold(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				30
+			));
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::hold(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				20
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				30
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Bar, &ALICE),
+				20
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::total_balance_on_hold(DOT, &ALICE),
+				50
+			);
+			assert_eq!(Tokens::holds(ALICE, DOT).len(), 2);
+
+			// MaxHolds is 2
+			assert!(!<Tokens as fungibles::InspectHold<_>>::hold_available(
+				DOT,
+				&TestId::Baz,
+				&ALICE
+			));
+			assert!(!<Tokens as fungibles::InspectHold<_>>::can_hold(
+				DOT,
+				&TestId::Baz,
+				&ALICE,
+				1
+			));
+			assert_noop!(
+				<Tokens as fungibles::MutateHold<_>>::hold(DOT, &TestId::Baz, &ALICE, 1),
+				Error::<Runtime>::TooManyHolds
+			);
+
+			// cannot release funds held for another reason
+			assert_noop!(
+				<Tokens as fungibles::MutateHold<_>>::release(DOT, &TestId::Bar, &ALICE, 30, Precision::Exact),
+				Error::<Runtime>::BalanceTooLow
+			);
+			assert_eq!(
+				<Tokens as fungibles::MutateHold<_>>::release(DOT, &TestId::Bar, &ALICE, 30, Precision::BestEffort),
+				Ok(20)
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Bar, &ALICE),
+				0
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				30
+			);
+			assert_eq!(Tokens::holds(ALICE, DOT).len(), 1);
+			assert_eq!(Tokens::accounts(ALICE, DOT).reserved, 30);
+			assert_eq!(Tokens::accounts(ALICE, DOT).free, 70);
+
+			// cannot transfer funds held for another reason
+			assert_noop!(
+				<Tokens as fungibles::MutateHold<_>>::transfer_on_hold(
+					DOT,
+					&TestId::Bar,
+					&ALICE,
+					&BOB,
+					10,
+					Precision::Exact,
+					Restriction::OnHold,
+					Fortitude::Polite
+				),
+				Error::<Runtime>::BalanceTooLow
+			);
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::transfer_on_hold(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				&BOB,
+				10,
+				Precision::Exact,
+				Restriction::OnHold,
+				Fortitude::Polite
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				20
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &BOB),
+				10
+			);
+			assert_eq!(Tokens::accounts(BOB, DOT).reserved, 10);
+
+			// anonymous reserves are not counted as held for any reason
+			assert_ok!(<Tokens as MultiReservableCurrency<_>>::reserve(DOT, &BOB, 5));
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &BOB),
+				10
+			);
+			assert_eq!(
+				<Tokens as fungibles::MutateHold<_>>::release(DOT, &TestId::Foo, &BOB, 15, Precision::BestEffort),
+				Ok(10)
+			);
+			assert_eq!(Tokens::accounts(BOB, DOT).reserved, 5);
+			assert!(!Holds::<Runtime>::contains_key(BOB, DOT));
+		});
+}
+
+#[test]
+fn anonymous_reserve_operations_should_not_touch_named_or_held_balance() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::hold(DOT, REASON, &ALICE, 30));
+			assert_ok!(<Tokens as NamedMultiReservableCurrency<_>>::reserve_named(
+				&RID_1, DOT, &ALICE, 20
+			));
+			assert_ok!(<Tokens as MultiReservableCurrency<_>>::reserve(DOT, &ALICE, 10));
+			assert_eq!(Tokens::accounts(ALICE, DOT).reserved, 60);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::total_balance_on_hold(DOT, &ALICE),
+				60
+			);
+
+			assert_eq!(<Tokens as MultiReservableCurrency<_>>::unreserve(DOT, &ALICE, 15), 5);
+			assert_eq!(Tokens::accounts(ALICE, DOT).reserved, 50);
+			assert_eq!(
+				<Tokens as MultiReservableCurrency<_>>::slash_reserved(DOT, &ALICE, 15),
+				15
+			);
+			assert_eq!(
+				<Tokens as MultiReservableCurrency<_>>::repatriate_reserved(DOT, &ALICE, &BOB, 15, BalanceStatus::Free),
+				Ok(15)
+			);
+			assert_eq!(Tokens::accounts(ALICE, DOT).reserved, 50);
+			assert_eq!(Tokens::accounts(BOB, DOT).free, 100);
+
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, REASON, &ALICE),
+				30
+			);
+			assert_eq!(
+				<Tokens as NamedMultiReservableCurrency<_>>::reserved_balance_named(&RID_1, DOT, &ALICE),
+				20
+			);
+			assert_ok!(Tokens::do_try_state());
+
+			assert_eq!(
+				<Tokens as fungibles::MutateHold<_>>::release(DOT, REASON, &ALICE, 30, Precision::Exact),
+				Ok(30)
+			);
+			assert_eq!(
+				<Tokens as NamedMultiReservableCurrency<_>>::unreserve_named(&RID_1, DOT, &ALICE, 20),
+				0
+			);
+			assert_eq!(Tokens::accounts(ALICE, DOT).reserved, 0);
+			assert_eq!(Tokens::accounts(ALICE, DOT).free, 100);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn fungibles_unbalanced_hold_should_only_touch_reason() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::hold(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				30
+			));
+			assert_ok!(<Tokens as fungibles::UnbalancedHold<_>>::set_balance_on_hold(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				20
+			));
+			assert_eq!(Tokens::accounts(ALICE, DOT).reserved, 50);
+			assert_eq!(Tokens::accounts(ALICE, DOT).free, 50);
+
+			assert_ok!(<Tokens as fungibles::UnbalancedHold<_>>::set_balance_on_hold(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				10
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				10
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Bar, &ALICE),
+				20
+			);
+			assert_eq!(Tokens::accounts(ALICE, DOT).reserved, 30);
+			assert_eq!(Tokens::accounts(ALICE, DOT).free, 70);
+
+			assert_noop!(
+				<Tokens as fungibles::UnbalancedHold<_>>::set_balance_on_hold(DOT, &TestId::Baz, &ALICE, 1),
+				Error::<Runtime>::TooManyHolds
+			);
+			assert_noop!(
+				<Tokens as fungibles::UnbalancedHold<_>>::set_balance_on_hold(DOT, &TestId::Bar, &ALICE, 91),
+				TokenError::BelowMinimum
+			);
+		});
+}
+
+#[test]
+fn fungibles_inspect_freeze_trait_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &TestId::Foo, &ALICE),
+				0
+			);
+			assert!(<Tokens as fungibles::InspectFreeze<_>>::can_freeze(
+				DOT,
+				&TestId::Foo,
+				&ALICE
+			));
+
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				10
+			));
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				20
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &TestId::Foo, &ALICE),
+				10
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &TestId::Bar, &ALICE),
+				20
+			);
+			// MaxFreezes is 2
+			assert!(<Tokens as fungibles::InspectFreeze<_>>::can_freeze(
+				DOT,
+				&TestId::Foo,
+				&ALICE
+			));
+			assert!(!<Tokens as fungibles::InspectFreeze<_>>::can_freeze(
+				DOT,
+				&TestId::Baz,
+				&ALICE
+			));
+		});
+}
+
+#[test]
+fn fungibles_mutate_freeze_trait_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				30
+			));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::FreezeSet {
+				freeze_id: TestId::Foo,
+				currency_id: DOT,
+				who: ALICE,
+				amount: 30,
+			}));
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 30);
+			assert_eq!(System::consumers(&ALICE), 1);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::reducible_balance(
+					DOT,
+					&ALICE,
+					Preservation::Expendable,
+					Fortitude::Polite
+				),
+				70
+			);
+
+			// frozen is the max of locks and freezes
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 50));
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 50);
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::extend_freeze(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				20
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &TestId::Foo, &ALICE),
+				30
+			);
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::extend_freeze(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				60
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &TestId::Foo, &ALICE),
+				60
+			);
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 60);
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::transfer(DOT, &ALICE, &BOB, 41, Preservation::Expendable),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				1
+			));
+			assert_noop!(
+				<Tokens as fungibles::MutateFreeze<_>>::set_freeze(DOT, &TestId::Baz, &ALICE, 1),
+				Error::<Runtime>::TooManyFreezes
+			);
+
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::thaw(DOT, &TestId::Foo, &ALICE));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::FreezeRemoved {
+				freeze_id: TestId::Foo,
+				currency_id: DOT,
+				who: ALICE,
+			}));
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 50);
+			assert_ok!(Tokens::remove_lock(ID_1, DOT, &ALICE));
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 1);
+
+			// setting a zero freeze removes it
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				0
+			));
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 0);
+			assert!(!Freezes::<Runtime>::contains_key(ALICE, DOT));
+			assert_eq!(System::consumers(&ALICE), 0);
+		});
+}
+
+#[test]
+fn fungibles_approvals_trait_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				<Tokens as fungibles::approvals::Inspect<_>>::allowance(DOT, &ALICE, &BOB),
+				0
+			);
+			assert_ok!(<Tokens as fungibles::approvals::Mutate<_>>::approve(
+				DOT, &ALICE, &BOB, 50
+			));
+			assert_eq!(
+				<Tokens as fungibles::approvals::Inspect<_>>::allowance(DOT, &ALICE, &BOB),
+				50
+			);
+			assert_noop!(
+				<Tokens as fungibles::approvals::Mutate<_>>::transfer_from(DOT, &ALICE, &BOB, &CHARLIE, 60),
+				Error::<Runtime>::Unapproved
+			);
+			assert_ok!(<Tokens as fungibles::approvals::Mutate<_>>::transfer_from(
+				DOT, &ALICE, &BOB, &CHARLIE, 10
+			));
+			assert_eq!(
+				<Tokens as fungibles::approvals::Inspect<_>>::allowance(DOT, &ALICE, &BOB),
+				40
+			);
+			assert_eq!(<Tokens as fungibles::Inspect<_>>::balance(DOT, &CHARLIE), 10);
+		});
+}
+
 #[test]
 fn fungibles_inspect_convert_should_work() {
 	pub struct ConvertBalanceTest;
@@ -722,3 +1119,325 @@ fn fungibles_mutate_convert_should_work() {
 			);
 		});
 }
+
+#[test]
+fn fungibles_hold_convert_should_work() {
+	pub struct ConvertBalanceTest;
+	impl ConvertBalance<Balance, Balance> for ConvertBalanceTest {
+		type AssetId = CurrencyId;
+		fn convert_balance(balance: Balance, _asset_id: CurrencyId) -> Result<Balance, ArithmeticError> {
+			Ok(balance * 100)
+		}
+
+		fn convert_balance_back(balance: Balance, _asset_id: CurrencyId) -> Result<Balance, ArithmeticError> {
+			Ok(balance / 100)
+		}
+	}
+
+	pub struct IsLiquidToken;
+	impl Contains<CurrencyId> for IsLiquidToken {
+		fn contains(currency_id: &CurrencyId) -> bool {
+			matches!(currency_id, &DOT)
+		}
+	}
+
+	pub struct GetCurrencyId;
+	impl Get<CurrencyId> for GetCurrencyId {
+		fn get() -> CurrencyId {
+			DOT
+		}
+	}
+
+	type RebaseTokens = Combiner<
+		AccountId,
+		IsLiquidToken,
+		Mapper<AccountId, Tokens, ConvertBalanceTest, Balance, GetCurrencyId>,
+		Tokens,
+	>;
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 300), (ALICE, BTC, 100), (BOB, DOT, 200)])
+		.build()
+		.execute_with(|| {
+			// `DOT` is routed through the mapper
+			assert!(<RebaseTokens as fungibles::InspectHold<AccountId>>::can_hold(
+				DOT, REASON, &ALICE, 10000
+			));
+			assert_ok!(<RebaseTokens as fungibles::MutateHold<AccountId>>::hold(
+				DOT, REASON, &ALICE, 10000
+			));
+			assert_eq!(
+				<RebaseTokens as fungibles::InspectHold<AccountId>>::balance_on_hold(DOT, REASON, &ALICE),
+				10000
+			);
+			assert_eq!(
+				<RebaseTokens as fungibles::InspectHold<AccountId>>::total_balance_on_hold(DOT, &ALICE),
+				10000
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<AccountId>>::balance_on_hold(DOT, REASON, &ALICE),
+				100
+			);
+			assert_eq!(
+				<RebaseTokens as fungibles::MutateHold<AccountId>>::release(
+					DOT,
+					REASON,
+					&ALICE,
+					5000,
+					Precision::Exact
+				),
+				Ok(5000)
+			);
+			assert_eq!(
+				<RebaseTokens as fungibles::MutateHold<AccountId>>::transfer_on_hold(
+					DOT,
+					REASON,
+					&ALICE,
+					&BOB,
+					5000,
+					Precision::Exact,
+					Restriction::Free,
+					Fortitude::Polite
+				),
+				Ok(5000)
+			);
+			assert_eq!(
+				<RebaseTokens as fungibles::InspectHold<AccountId>>::balance_on_hold(DOT, REASON, &ALICE),
+				0
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 250);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 250);
+
+			// other currencies are routed to tokens directly
+			assert_ok!(<RebaseTokens as fungibles::MutateHold<AccountId>>::hold(
+				BTC, REASON, &ALICE, 30
+			));
+			assert_eq!(
+				<RebaseTokens as fungibles::InspectHold<AccountId>>::balance_on_hold(BTC, REASON, &ALICE),
+				30
+			);
+			assert_eq!(Tokens::reserved_balance(BTC, &ALICE), 30);
+			assert_eq!(
+				<RebaseTokens as fungibles::MutateHold<AccountId>>::release(BTC, REASON, &ALICE, 30, Precision::Exact),
+				Ok(30)
+			);
+			assert_eq!(Tokens::reserved_balance(BTC, &ALICE), 0);
+		});
+}
+
+#[test]
+fn fungibles_freeze_convert_should_work() {
+	pub struct ConvertBalanceTest;
+	impl ConvertBalance<Balance, Balance> for ConvertBalanceTest {
+		type AssetId = CurrencyId;
+		fn convert_balance(balance: Balance, _asset_id: CurrencyId) -> Result<Balance, ArithmeticError> {
+			Ok(balance * 100)
+		}
+
+		fn convert_balance_back(balance: Balance, _asset_id: CurrencyId) -> Result<Balance, ArithmeticError> {
+			Ok(balance / 100)
+		}
+	}
+
+	pub struct IsLiquidToken;
+	impl Contains<CurrencyId> for IsLiquidToken {
+		fn contains(currency_id: &CurrencyId) -> bool {
+			matches!(currency_id, &DOT)
+		}
+	}
+
+	pub struct GetCurrencyId;
+	impl Get<CurrencyId> for GetCurrencyId {
+		fn get() -> CurrencyId {
+			DOT
+		}
+	}
+
+	type RebaseTokens = Combiner<
+		AccountId,
+		IsLiquidToken,
+		Mapper<AccountId, Tokens, ConvertBalanceTest, Balance, GetCurrencyId>,
+		Tokens,
+	>;
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 300), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			// `DOT` is routed through the mapper
+			assert!(<RebaseTokens as fungibles::InspectFreeze<AccountId>>::can_freeze(
+				DOT, REASON, &ALICE
+			));
+			assert_ok!(<RebaseTokens as fungibles::MutateFreeze<AccountId>>::set_freeze(
+				DOT, REASON, &ALICE, 10000
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<AccountId>>::balance_frozen(DOT, REASON, &ALICE),
+				100
+			);
+			assert_ok!(<RebaseTokens as fungibles::MutateFreeze<AccountId>>::extend_freeze(
+				DOT, REASON, &ALICE, 20000
+			));
+			assert_eq!(
+				<RebaseTokens as fungibles::InspectFreeze<AccountId>>::balance_frozen(DOT, REASON, &ALICE),
+				20000
+			);
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 200);
+			assert_ok!(<RebaseTokens as fungibles::MutateFreeze<AccountId>>::thaw(
+				DOT, REASON, &ALICE
+			));
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 0);
+
+			// other currencies are routed to tokens directly
+			assert_ok!(<RebaseTokens as fungibles::MutateFreeze<AccountId>>::set_freeze(
+				BTC, REASON, &ALICE, 50
+			));
+			assert_eq!(
+				<RebaseTokens as fungibles::InspectFreeze<AccountId>>::balance_frozen(BTC, REASON, &ALICE),
+				50
+			);
+			assert_eq!(Tokens::accounts(ALICE, BTC).frozen, 50);
+			assert_ok!(<RebaseTokens as fungibles::MutateFreeze<AccountId>>::thaw(
+				BTC, REASON, &ALICE
+			));
+			assert_eq!(Tokens::accounts(ALICE, BTC).frozen, 0);
+		});
+}
+
+#[test]
+fn decimals_convert_balance_should_work() {
+	use orml_traits::asset_registry::{AssetMetadata, Inspect};
+	use sp_arithmetic::per_things::Rounding;
+	use xcm::v3::MultiLocation;
+
+	pub struct MockRegistry;
+	impl Inspect for MockRegistry {
+		type AssetId = CurrencyId;
+		type Balance = Balance;
+		type CustomMetadata = ();
+		type StringLimit = ConstU32<32>;
+
+		fn asset_id(_location: &MultiLocation) -> Option<CurrencyId> {
+			None
+		}
+
+		fn metadata(asset_id: &CurrencyId) -> Option<AssetMetadata<Balance, (), ConstU32<32>>> {
+			let decimals = match *asset_id {
+				DOT => 10,
+				BTC => 8,
+				KSM => 60,
+				_ => return None,
+			};
+			Some(AssetMetadata {
+				decimals,
+				name: Default::default(),
+				symbol: Default::default(),
+				existential_deposit: 0,
+				location: None,
+				additional: (),
+			})
+		}
+
+		fn metadata_by_location(_location: &MultiLocation) -> Option<AssetMetadata<Balance, (), ConstU32<32>>> {
+			None
+		}
+
+		fn location(_asset_id: &CurrencyId) -> Result<Option<MultiLocation>, DispatchError> {
+			Ok(None)
+		}
+	}
+
+	pub struct RoundDown;
+	impl Get<Rounding> for RoundDown {
+		fn get() -> Rounding {
+			Rounding::Down
+		}
+	}
+
+	pub struct RoundUp;
+	impl Get<Rounding> for RoundUp {
+		fn get() -> Rounding {
+			Rounding::Up
+		}
+	}
+
+	pub struct GetCurrencyId;
+	impl Get<CurrencyId> for GetCurrencyId {
+		fn get() -> CurrencyId {
+			DOT
+		}
+	}
+
+	type Down = DecimalsConvertBalance<MockRegistry, ConstU32<12>, RoundDown>;
+	type Up = DecimalsConvertBalance<MockRegistry, ConstU32<12>, RoundUp>;
+
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance(123, DOT),
+		Ok(12300)
+	);
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance(1, BTC),
+		Ok(10000)
+	);
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance_back(12399, DOT),
+		Ok(123)
+	);
+	assert_eq!(
+		<Up as ConvertBalance<Balance, Balance>>::convert_balance_back(12301, DOT),
+		Ok(124)
+	);
+	assert_eq!(
+		<Up as ConvertBalance<Balance, Balance>>::convert_balance_back(12300, DOT),
+		Ok(123)
+	);
+
+	// overflow
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance(Balance::max_value(), DOT),
+		Err(ArithmeticError::Overflow)
+	);
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance_saturated(Balance::max_value(), DOT),
+		Balance::max_value()
+	);
+
+	// reducing the precision by more than 38 digits
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance(Balance::max_value(), KSM),
+		Ok(0)
+	);
+	assert_eq!(<Up as ConvertBalance<Balance, Balance>>::convert_balance(1, KSM), Ok(1));
+	assert_eq!(<Up as ConvertBalance<Balance, Balance>>::convert_balance(0, KSM), Ok(0));
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance_back(1, KSM),
+		Err(ArithmeticError::Overflow)
+	);
+
+	// unknown asset
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance(100, ETH),
+		Err(ArithmeticError::Underflow)
+	);
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance_back(100, ETH),
+		Err(ArithmeticError::Underflow)
+	);
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance_saturated(100, ETH),
+		0
+	);
+	assert_eq!(
+		<Down as ConvertBalance<Balance, Balance>>::convert_balance_back_saturated(100, ETH),
+		0
+	);
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			type RebaseDot = Mapper<AccountId, Tokens, Down, Balance, GetCurrencyId>;
+			assert_eq!(<RebaseDot as fungible::Inspect<AccountId>>::balance(&ALICE), 10000);
+			assert_eq!(<RebaseDot as fungible::Inspect<AccountId>>::total_issuance(), 10000);
+		});
+}
@@ -0,0 +1,427 @@
+//! Unit tests for the migrations.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	storage::{storage_prefix, unhashed},
+	traits::{ConstU32, OnIdle, OnRuntimeUpgrade},
+	weights::Weight,
+	Blake2_128Concat, StorageHasher,
+};
+use migrations::{
+	MigrateToHolds, MigrateToTokens, MigrateToV1, MigrateToV2, MigrationSource, MigrationStatus, MigrationStatuses,
+	MultiBlockMigration, PalletAssets, PalletBalances,
+};
+use mock::*;
+use sp_runtime::traits::Convert;
+
+parameter_types! {
+	pub const AssetsPalletName: &'static str = "Assets";
+	pub LegacyHolds: Vec<(AccountId, CurrencyId, TestId, Balance)> = vec![
+		(ALICE, DOT, TestId::Foo, 30),
+		(ALICE, DOT, TestId::Bar, 40),
+		(BOB, DOT, TestId::Foo, 10),
+	];
+}
+
+pub struct AssetIdToCurrencyId;
+impl Convert<u32, Option<CurrencyId>> for AssetIdToCurrencyId {
+	fn convert(asset_id: u32) -> Option<CurrencyId> {
+		match asset_id {
+			1 => Some(DOT),
+			2 => Some(BTC),
+			_ => None,
+		}
+	}
+}
+
+type AssetsSource = PalletAssets<AssetsPalletName, u32, AssetIdToCurrencyId>;
+type AssetsMigration = MigrateToTokens<Runtime, AssetsSource, ConstU32<2>>;
+type BalancesSource = PalletBalances<BalancesPalletName, GetBtcId>;
+
+fn put_asset(asset_id: u32, supply: Balance) {
+	let key = [
+		&storage_prefix(b"Assets", b"Asset")[..],
+		&Blake2_128Concat::hash(&asset_id.encode())[..],
+	]
+	.concat();
+	// owner, issuer, admin, freezer, supply and the other fields
+	unhashed::put(&key, &(ALICE, ALICE, ALICE, ALICE, supply, 0u128, 1u128, false));
+}
+
+fn put_asset_account(asset_id: u32, who: AccountId, balance: Balance) {
+	let key = [
+		&storage_prefix(b"Assets", b"Account")[..],
+		&Blake2_128Concat::hash(&asset_id.encode())[..],
+		&Blake2_128Concat::hash(&who.encode())[..],
+	]
+	.concat();
+	// balance, status, reason and extra
+	unhashed::put(&key, &(balance, 0u8, 0u8));
+}
+
+fn put_balances_account(who: AccountId, free: Balance, reserved: Balance) {
+	let key = [
+		&storage_prefix(b"Balances", b"Account")[..],
+		&Blake2_128Concat::hash(&who.encode())[..],
+	]
+	.concat();
+	// free, reserved, frozen and flags
+	unhashed::put(&key, &(free, reserved, 0u128, 0u128));
+}
+
+#[test]
+fn migrate_from_pallet_assets_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 10)])
+		.build()
+		.execute_with(|| {
+			put_asset(1, 150);
+			put_asset(2, 30);
+			put_asset(3, 999);
+			put_asset_account(1, ALICE, 100);
+			put_asset_account(1, BOB, 50);
+			put_asset_account(2, ALICE, 30);
+			put_asset_account(3, ALICE, 999);
+
+			let mut total_issuance =
+				<AssetsSource as MigrationSource<AccountId, CurrencyId, Balance>>::total_issuance();
+			total_issuance.sort();
+			assert_eq!(total_issuance, vec![(DOT, 150), (BTC, 30)]);
+
+			// not started
+			AssetsMigration::on_idle(1, Weight::MAX);
+			assert_eq!(Tokens::total_issuance(DOT), 10);
+
+			AssetsMigration::on_runtime_upgrade();
+			assert_eq!(
+				MigrationStatuses::<Runtime>::get(b"Assets".to_vec()),
+				Some(MigrationStatus::Ongoing { cursor: vec![] })
+			);
+
+			AssetsMigration::on_idle(1, Weight::MAX);
+			assert!(matches!(
+				MigrationStatuses::<Runtime>::get(b"Assets".to_vec()),
+				Some(MigrationStatus::Ongoing { .. })
+			));
+			assert_ok!(Tokens::do_try_state());
+			AssetsMigration::on_idle(2, Weight::MAX);
+			AssetsMigration::on_idle(3, Weight::MAX);
+			assert_eq!(
+				MigrationStatuses::<Runtime>::get(b"Assets".to_vec()),
+				Some(MigrationStatus::Completed)
+			);
+
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 110);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 50);
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 30);
+			assert_eq!(Tokens::total_issuance(DOT), 160);
+			assert_eq!(Tokens::total_issuance(BTC), 30);
+			assert_ok!(Tokens::do_try_state());
+
+			// can not run twice
+			AssetsMigration::on_runtime_upgrade();
+			AssetsMigration::on_idle(4, Weight::MAX);
+			assert_eq!(Tokens::total_issuance(DOT), 160);
+		});
+}
+
+#[test]
+fn migrate_from_pallet_balances_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		unhashed::put(&storage_prefix(b"Balances", b"TotalIssuance"), &100u128);
+		put_balances_account(ALICE, 50, 20);
+		put_balances_account(BOB, 30, 0);
+
+		assert_eq!(
+			<BalancesSource as MigrationSource<AccountId, CurrencyId, Balance>>::total_issuance(),
+			vec![(BTC, 100)]
+		);
+
+		BalancesMigration::on_runtime_upgrade();
+		BalancesMigration::on_idle(1, Weight::MAX);
+		assert_eq!(
+			MigrationStatuses::<Runtime>::get(b"Balances".to_vec()),
+			Some(MigrationStatus::Ongoing {
+				cursor: [
+					&storage_prefix(b"Balances", b"Account")[..],
+					&Blake2_128Concat::hash(&BOB.encode()).max(Blake2_128Concat::hash(&ALICE.encode()))[..],
+				]
+				.concat()
+			})
+		);
+		BalancesMigration::on_idle(2, Weight::MAX);
+		assert_eq!(
+			MigrationStatuses::<Runtime>::get(b"Balances".to_vec()),
+			Some(MigrationStatus::Completed)
+		);
+
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 50);
+		assert_eq!(Tokens::reserved_balance(BTC, &ALICE), 20);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 30);
+		assert_eq!(Tokens::total_issuance(BTC), 100);
+		assert_ok!(Tokens::do_try_state());
+	});
+}
+
+#[test]
+fn multi_block_migrations_should_complete_on_idle() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, BTC, 10)])
+		.build()
+		.execute_with(|| {
+			unhashed::put(&storage_prefix(b"Balances", b"TotalIssuance"), &100u64);
+			put_balances_account(ALICE, 40, 10);
+			put_balances_account(BOB, 30, 0);
+			put_balances_account(CHARLIE, 20, 0);
+
+			BalancesMigration::on_runtime_upgrade();
+			// the migration doesn't start before `on_idle`
+			assert_eq!(Tokens::total_issuance(BTC), 10);
+
+			let mut block_number = 1;
+			while MigrationStatuses::<Runtime>::get(b"Balances".to_vec()) != Some(MigrationStatus::Completed) {
+				assert!(block_number < 10, "the migration should complete");
+				<Tokens as OnIdle<_>>::on_idle(block_number, Weight::MAX);
+				block_number += 1;
+			}
+			// 3 accounts in batches of 2
+			assert_eq!(block_number, 3);
+
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 50);
+			assert_eq!(Tokens::reserved_balance(BTC, &ALICE), 10);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 30);
+			assert_eq!(Tokens::free_balance(BTC, &CHARLIE), 20);
+			assert_eq!(Tokens::total_issuance(BTC), 110);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+type LocksMigrations = (MigrateToV1<Runtime, ConstU32<1>>, MigrateToV2<Runtime, ConstU32<1>>);
+
+#[test]
+fn migrate_to_v1_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			StorageVersion::new(0).put::<Tokens>();
+			// `BalanceLock` before locks could expire
+			unhashed::put(
+				&Locks::<Runtime>::hashed_key_for(ALICE, DOT),
+				&vec![(ID_1, 30u64), (ID_2, 50u64)],
+			);
+			unhashed::put(&Locks::<Runtime>::hashed_key_for(BOB, DOT), &vec![(ID_1, 20u64)]);
+
+			// not started
+			MigrateToV1::<Runtime, ConstU32<1>>::on_idle(1, Weight::MAX);
+			assert_eq!(Tokens::on_chain_storage_version(), 0);
+
+			MigrateToV1::<Runtime, ConstU32<1>>::on_runtime_upgrade();
+			assert_eq!(
+				MigrationStatuses::<Runtime>::get(b"LocksV1".to_vec()),
+				Some(MigrationStatus::Ongoing { cursor: vec![] })
+			);
+
+			// one account per block
+			MigrateToV1::<Runtime, ConstU32<1>>::on_idle(1, Weight::MAX);
+			assert_eq!(Tokens::on_chain_storage_version(), 0);
+			assert!(matches!(
+				MigrationStatuses::<Runtime>::get(b"LocksV1".to_vec()),
+				Some(MigrationStatus::Ongoing { .. })
+			));
+			MigrateToV1::<Runtime, ConstU32<1>>::on_idle(2, Weight::MAX);
+			MigrateToV1::<Runtime, ConstU32<1>>::on_idle(3, Weight::MAX);
+			assert_eq!(
+				MigrationStatuses::<Runtime>::get(b"LocksV1".to_vec()),
+				Some(MigrationStatus::Completed)
+			);
+			assert_eq!(Tokens::on_chain_storage_version(), 1);
+
+			// `BalanceLock` before locks had withdraw reasons
+			assert_eq!(
+				unhashed::get::<Vec<(LockIdentifier, u64, Option<u64>)>>(&Locks::<Runtime>::hashed_key_for(ALICE, DOT)),
+				Some(vec![(ID_1, 30, None), (ID_2, 50, None)])
+			);
+			assert_eq!(
+				unhashed::get::<Vec<(LockIdentifier, u64, Option<u64>)>>(&Locks::<Runtime>::hashed_key_for(BOB, DOT)),
+				Some(vec![(ID_1, 20, None)])
+			);
+
+			// can not run twice
+			MigrateToV1::<Runtime, ConstU32<1>>::on_runtime_upgrade();
+			MigrateToV1::<Runtime, ConstU32<1>>::on_idle(4, Weight::MAX);
+			assert_eq!(
+				unhashed::get::<Vec<(LockIdentifier, u64, Option<u64>)>>(&Locks::<Runtime>::hashed_key_for(BOB, DOT)),
+				Some(vec![(ID_1, 20, None)])
+			);
+		});
+}
+
+#[test]
+fn migrate_to_v2_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			StorageVersion::new(1).put::<Tokens>();
+			// `BalanceLock` before locks had withdraw reasons
+			unhashed::put(
+				&Locks::<Runtime>::hashed_key_for(ALICE, DOT),
+				&vec![(ID_1, 30u64, None::<u64>), (ID_2, 50u64, Some(10u64))],
+			);
+
+			MigrateToV2::<Runtime, ConstU32<1>>::on_runtime_upgrade();
+			MigrateToV2::<Runtime, ConstU32<1>>::on_idle(1, Weight::MAX);
+			MigrateToV2::<Runtime, ConstU32<1>>::on_idle(2, Weight::MAX);
+			assert_eq!(
+				MigrationStatuses::<Runtime>::get(b"LocksV2".to_vec()),
+				Some(MigrationStatus::Completed)
+			);
+			assert_eq!(Tokens::on_chain_storage_version(), 2);
+			assert_eq!(
+				Tokens::locks(ALICE, DOT).into_inner(),
+				vec![
+					BalanceLock {
+						id: ID_1,
+						amount: 30,
+						expires_at: None,
+						reasons: LockReasons::default(),
+					},
+					BalanceLock {
+						id: ID_2,
+						amount: 50,
+						expires_at: Some(10),
+						reasons: LockReasons::default(),
+					},
+				]
+			);
+
+			// already migrated
+			MigrationStatuses::<Runtime>::remove(b"LocksV2".to_vec());
+			MigrateToV2::<Runtime, ConstU32<1>>::on_runtime_upgrade();
+			assert_eq!(MigrationStatuses::<Runtime>::get(b"LocksV2".to_vec()), None);
+			assert_eq!(Tokens::locks(ALICE, DOT).len(), 2);
+		});
+}
+
+#[test]
+fn migrate_locks_should_run_in_order() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			StorageVersion::new(0).put::<Tokens>();
+			unhashed::put(&Locks::<Runtime>::hashed_key_for(ALICE, DOT), &vec![(ID_1, 30u64)]);
+			unhashed::put(&Locks::<Runtime>::hashed_key_for(BOB, DOT), &vec![(ID_2, 20u64)]);
+
+			LocksMigrations::on_runtime_upgrade();
+			// `MigrateToV2` waits for `MigrateToV1`
+			LocksMigrations::on_idle(1, Weight::MAX);
+			assert_eq!(Tokens::on_chain_storage_version(), 0);
+			assert_eq!(
+				MigrationStatuses::<Runtime>::get(b"LocksV2".to_vec()),
+				Some(MigrationStatus::Ongoing { cursor: vec![] })
+			);
+
+			let mut block_number = 2;
+			while Tokens::on_chain_storage_version() < 2 {
+				assert!(block_number < 10, "the migrations should complete");
+				LocksMigrations::on_idle(block_number, Weight::MAX);
+				block_number += 1;
+			}
+			assert_eq!(
+				MigrationStatuses::<Runtime>::get(b"LocksV1".to_vec()),
+				Some(MigrationStatus::Completed)
+			);
+			assert_eq!(
+				MigrationStatuses::<Runtime>::get(b"LocksV2".to_vec()),
+				Some(MigrationStatus::Completed)
+			);
+			assert_eq!(Tokens::locks(ALICE, DOT).into_inner()[0].amount, 30);
+			assert_eq!(Tokens::locks(BOB, DOT).into_inner()[0].amount, 20);
+		});
+}
+
+#[test]
+fn locks_should_not_change_while_migrating() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.locks(vec![(ALICE, DOT, ID_1, 30)])
+		.build()
+		.execute_with(|| {
+			StorageVersion::new(1).put::<Tokens>();
+			unhashed::put(
+				&Locks::<Runtime>::hashed_key_for(ALICE, DOT),
+				&vec![(ID_1, 30u64, Some(1u64))],
+			);
+			MigrateToV2::<Runtime, ConstU32<1>>::on_runtime_upgrade();
+
+			assert_noop!(
+				Tokens::set_lock(ID_2, DOT, &ALICE, 50),
+				Error::<Runtime>::LocksMigrating
+			);
+			assert_noop!(
+				<Tokens as fungibles::MutateFreeze<_>>::set_freeze(DOT, &TestId::Foo, &ALICE, 50),
+				Error::<Runtime>::LocksMigrating
+			);
+			// the frozen balance is kept although the lock expired
+			System::set_block_number(2);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 30);
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 61),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+
+			MigrateToV2::<Runtime, ConstU32<1>>::on_idle(2, Weight::MAX);
+			MigrateToV2::<Runtime, ConstU32<1>>::on_idle(3, Weight::MAX);
+			assert_eq!(Tokens::on_chain_storage_version(), 2);
+			assert_ok!(Tokens::set_lock(ID_2, DOT, &ALICE, 50));
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 50);
+		});
+}
+
+#[test]
+fn migrate_to_holds_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// holds were only added to the reserved balance
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 50));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 20));
+
+			MigrateToHolds::<Runtime, LegacyHolds>::on_runtime_upgrade();
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				30
+			);
+			// capped by the reserved balance which is neither named nor held
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Bar, &ALICE),
+				20
+			);
+			// nothing is reserved
+			assert!(Tokens::holds(BOB, DOT).is_empty());
+			assert_ok!(Tokens::do_try_state());
+
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::release(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				30,
+				Precision::Exact
+			));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 60);
+
+			// already migrated
+			MigrateToHolds::<Runtime, LegacyHolds>::on_runtime_upgrade();
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				0
+			);
+		});
+}
@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnIdle};
 use mock::*;
 
 #[test]
@@ -151,6 +151,181 @@ fn multi_lockable_currency_remove_lock_work() {
 		});
 }
 
+#[test]
+fn multi_lockable_currency_lock_reasons_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock_with_reasons(
+				ID_1,
+				DOT,
+				&ALICE,
+				50,
+				WithdrawReasons::TRANSFER
+			));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 50);
+			assert_noop!(
+				<Tokens as MultiCurrency<_>>::transfer(DOT, &ALICE, &BOB, 60),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert!(Tokens::can_reserve(DOT, &ALICE, 60));
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 60));
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 60), 0);
+
+			assert_ok!(Tokens::extend_lock_with_reasons(
+				ID_1,
+				DOT,
+				&ALICE,
+				50,
+				WithdrawReasons::RESERVE
+			));
+			assert_eq!(
+				Tokens::locks(ALICE, DOT)[0].reasons,
+				LockReasons::from(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE)
+			);
+			assert!(!Tokens::can_reserve(DOT, &ALICE, 60));
+			assert_noop!(
+				Tokens::reserve(DOT, &ALICE, 60),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+
+			assert_ok!(Tokens::set_lock_with_reasons(
+				ID_1,
+				DOT,
+				&ALICE,
+				50,
+				WithdrawReasons::empty()
+			));
+			assert_eq!(Tokens::locks(ALICE, DOT).len(), 0);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 0);
+		});
+}
+
+#[test]
+fn multi_expiring_lockable_currency_set_lock_until_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// no-op if already expired
+			assert_ok!(Tokens::set_lock_until(ID_1, DOT, &ALICE, 10, 1));
+			assert_eq!(Tokens::locks(ALICE, DOT).len(), 0);
+
+			assert_ok!(Tokens::set_lock_until(ID_1, DOT, &ALICE, 50, 3));
+			assert_ok!(Tokens::set_lock(ID_2, DOT, &ALICE, 20));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 50);
+			assert_noop!(
+				Tokens::ensure_can_withdraw(DOT, &ALICE, 51),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+
+			// expired locks are ignored before they are pruned
+			System::set_block_number(3);
+			assert_eq!(Tokens::locks(ALICE, DOT).len(), 2);
+			assert_ok!(Tokens::ensure_can_withdraw(DOT, &ALICE, 80));
+			assert_noop!(
+				Tokens::ensure_can_withdraw(DOT, &ALICE, 81),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_ok!(Tokens::do_try_state());
+
+			// and pruned on the next mutation of the account
+			assert_ok!(<Tokens as MultiCurrency<_>>::transfer(DOT, &ALICE, &BOB, 80));
+			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
+			assert_eq!(Tokens::locks(ALICE, DOT)[0].id, ID_2);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 20);
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::LockRemoved {
+				lock_id: ID_1,
+				currency_id: DOT,
+				who: ALICE,
+			}));
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn multi_expiring_lockable_currency_extend_lock_until_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock_until(ID_1, DOT, &ALICE, 20, 5));
+			assert_ok!(Tokens::extend_lock_until(ID_1, DOT, &ALICE, 10, 10));
+			assert_eq!(
+				Tokens::locks(ALICE, DOT)[0],
+				BalanceLock {
+					id: ID_1,
+					amount: 20,
+					expires_at: Some(10),
+					reasons: LockReasons::default(),
+				}
+			);
+			assert_ok!(Tokens::extend_lock_until(ID_1, DOT, &ALICE, 30, 5));
+			assert_eq!(
+				Tokens::locks(ALICE, DOT)[0],
+				BalanceLock {
+					id: ID_1,
+					amount: 30,
+					expires_at: Some(10),
+					reasons: LockReasons::default(),
+				}
+			);
+
+			// a lock without expiry is never shortened
+			assert_ok!(Tokens::extend_lock(ID_1, DOT, &ALICE, 10));
+			assert_ok!(Tokens::extend_lock_until(ID_1, DOT, &ALICE, 10, 20));
+			assert_eq!(
+				Tokens::locks(ALICE, DOT)[0],
+				BalanceLock {
+					id: ID_1,
+					amount: 30,
+					expires_at: None,
+					reasons: LockReasons::default(),
+				}
+			);
+
+			// an expired lock is replaced
+			assert_ok!(Tokens::set_lock_until(ID_2, DOT, &ALICE, 50, 3));
+			System::set_block_number(3);
+			assert_ok!(Tokens::extend_lock_until(ID_2, DOT, &ALICE, 10, 6));
+			assert_eq!(
+				Tokens::locks(ALICE, DOT)[1],
+				BalanceLock {
+					id: ID_2,
+					amount: 10,
+					expires_at: Some(6),
+					reasons: LockReasons::default(),
+				}
+			);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 30);
+		});
+}
+
+#[test]
+fn expired_locks_should_be_pruned_on_idle() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock_until(ID_1, DOT, &ALICE, 50, 3));
+			assert_ok!(Tokens::set_lock_until(ID_1, DOT, &BOB, 50, 3));
+			assert_ok!(Tokens::set_lock(ID_2, DOT, &BOB, 10));
+
+			<Tokens as OnIdle<_>>::on_idle(2, Weight::MAX);
+			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
+			assert_eq!(Tokens::locks(BOB, DOT).len(), 2);
+
+			System::set_block_number(3);
+			<Tokens as OnIdle<_>>::on_idle(3, Weight::MAX);
+			assert!(!Locks::<Runtime>::contains_key(ALICE, DOT));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 0);
+			assert_eq!(Tokens::locks(BOB, DOT).len(), 1);
+			assert_eq!(Tokens::accounts(&BOB, DOT).frozen, 10);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
 #[test]
 fn multi_reservable_currency_can_reserve_work() {
 	ExtBuilder::default()
@@ -448,7 +623,13 @@ fn named_multi_reservable_currency_reserve_work() {
 			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 0);
 			assert_eq!(Tokens::total_balance(DOT, &ALICE), 100);
 			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 50));
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Reserved {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Reserved {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 50,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReservedNamed {
+				id: RID_1,
 				currency_id: DOT,
 				who: ALICE,
 				amount: 50,
@@ -459,7 +640,13 @@ fn named_multi_reservable_currency_reserve_work() {
 			assert_eq!(Tokens::total_balance(DOT, &ALICE), 100);
 
 			assert_ok!(Tokens::reserve_named(&RID_2, DOT, &ALICE, 50));
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Reserved {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Reserved {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 50,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReservedNamed {
+				id: RID_2,
 				currency_id: DOT,
 				who: ALICE,
 				amount: 50,
@@ -494,7 +681,13 @@ fn named_multi_reservable_currency_unreserve_work() {
 			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 0), 0);
 
 			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Reserved {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Reserved {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 30,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReservedNamed {
+				id: RID_1,
 				currency_id: DOT,
 				who: ALICE,
 				amount: 30,
@@ -504,7 +697,13 @@ fn named_multi_reservable_currency_unreserve_work() {
 			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
 
 			assert_ok!(Tokens::reserve_named(&RID_2, DOT, &ALICE, 30));
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Reserved {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Reserved {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 30,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReservedNamed {
+				id: RID_2,
 				currency_id: DOT,
 				who: ALICE,
 				amount: 30,
@@ -515,7 +714,13 @@ fn named_multi_reservable_currency_unreserve_work() {
 			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &ALICE), 30);
 
 			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 30), 0);
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Unreserved {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Unreserved {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 30,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::UnreservedNamed {
+				id: RID_1,
 				currency_id: DOT,
 				who: ALICE,
 				amount: 30,
@@ -526,7 +731,13 @@ fn named_multi_reservable_currency_unreserve_work() {
 			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &ALICE), 30);
 
 			assert_eq!(Tokens::unreserve_named(&RID_2, DOT, &ALICE, 30), 0);
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Unreserved {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Unreserved {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 30,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::UnreservedNamed {
+				id: RID_2,
 				currency_id: DOT,
 				who: ALICE,
 				amount: 30,
@@ -584,7 +795,15 @@ fn named_multi_reservable_currency_repatriate_reserved_work() {
 				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &ALICE, 30, BalanceStatus::Reserved),
 				Ok(0)
 			);
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriated {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriated {
+				currency_id: DOT,
+				from: BOB,
+				to: ALICE,
+				amount: 30,
+				status: BalanceStatus::Reserved,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriatedNamed {
+				id: RID_1,
 				currency_id: DOT,
 				from: BOB,
 				to: ALICE,
@@ -605,7 +824,15 @@ fn named_multi_reservable_currency_repatriate_reserved_work() {
 			);
 
 			// Actual amount repatriated is 20.
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriated {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriated {
+				currency_id: DOT,
+				from: BOB,
+				to: ALICE,
+				amount: 20,
+				status: BalanceStatus::Free,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriatedNamed {
+				id: RID_1,
 				currency_id: DOT,
 				from: BOB,
 				to: ALICE,
@@ -722,7 +949,15 @@ fn named_multi_reservable_repatriate_all_reserved_named_works() {
 			assert_eq!(Tokens::free_balance(DOT, &BOB), 100);
 			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 50);
 
-			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriated {
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriated {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 50,
+				status: BalanceStatus::Reserved,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ReserveRepatriatedNamed {
+				id: RID_1,
 				currency_id: DOT,
 				from: ALICE,
 				to: BOB,
@@ -800,3 +1035,67 @@ fn slash_hook_works_for_reserved_named() {
 			assert_eq!(OnSlashHook::<Runtime>::calls(), initial_slash_hook_calls + 1);
 		});
 }
+
+#[test]
+fn multi_currency_imbalance_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			use orml_traits::MultiCurrencyImbalance as Imbalances;
+
+			// dropping an issued imbalance gives the issuance back
+			let issued = <Tokens as Imbalances<_>>::issue(DOT, 50);
+			assert_eq!(issued.peek(), 50);
+			assert_eq!(Tokens::total_issuance(DOT), 150);
+			drop(issued);
+			assert_eq!(Tokens::total_issuance(DOT), 100);
+
+			// dropping a burned imbalance takes the issuance back
+			let burned = <Tokens as Imbalances<_>>::burn(BTC, 30);
+			assert_eq!(Tokens::total_issuance(BTC), 70);
+			drop(burned);
+			assert_eq!(Tokens::total_issuance(BTC), 100);
+
+			// depositing an issued imbalance squares it up
+			let issued = <Tokens as Imbalances<_>>::issue(DOT, 20);
+			let deposited = <Tokens as Imbalances<_>>::deposit_creating(DOT, &BOB, 20);
+			assert!(deposited.offset(issued).is_ok());
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 20);
+			assert_eq!(Tokens::total_issuance(DOT), 120);
+
+			// imbalances of different currencies can't be combined
+			let dot = <Tokens as Imbalances<_>>::issue(DOT, 10);
+			let btc = <Tokens as Imbalances<_>>::burn(BTC, 10);
+			let (dot, btc) = dot.offset(btc).err().unwrap();
+			let (dot, _) = dot.merge(MultiNegativeImbalance::zero(BTC)).err().unwrap();
+			drop((dot, btc));
+			assert_eq!(Tokens::total_issuance(DOT), 120);
+			assert_eq!(Tokens::total_issuance(BTC), 100);
+
+			// withdrawing and dropping destroys the funds
+			let withdrawn =
+				<Tokens as Imbalances<_>>::withdraw(DOT, &ALICE, 40, ExistenceRequirement::AllowDeath).unwrap();
+			assert_eq!(withdrawn.currency_id(), DOT);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 60);
+			assert_eq!(Tokens::total_issuance(DOT), 120);
+			drop(withdrawn);
+			assert_eq!(Tokens::total_issuance(DOT), 80);
+			assert!(<Tokens as Imbalances<_>>::withdraw(DOT, &ALICE, 59, ExistenceRequirement::KeepAlive).is_err());
+
+			// settling withdraws the imbalance from the account
+			let deposited = <Tokens as Imbalances<_>>::deposit_creating(DOT, &BOB, 10);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 30);
+			assert!(<Tokens as Imbalances<_>>::settle(&ALICE, deposited, ExistenceRequirement::AllowDeath).is_ok());
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::total_issuance(DOT), 80);
+
+			// an unsettled imbalance is returned
+			let deposited = <Tokens as Imbalances<_>>::deposit_creating(DOT, &BOB, 60);
+			let deposited =
+				<Tokens as Imbalances<_>>::settle(&ALICE, deposited, ExistenceRequirement::AllowDeath).unwrap_err();
+			assert_eq!(deposited.peek(), 60);
+			drop(deposited);
+			assert_eq!(Tokens::total_issuance(DOT), 140);
+		});
+}
@@ -34,6 +34,31 @@ pub trait WeightInfo {
 	fn transfer_keep_alive() -> Weight;
 	fn force_transfer() -> Weight;
 	fn set_balance() -> Weight;
+	fn approve_transfer() -> Weight;
+	fn cancel_approval() -> Weight;
+	fn transfer_approved() -> Weight;
+	fn freeze_currency() -> Weight;
+	fn thaw_currency() -> Weight;
+	fn freeze_account() -> Weight;
+	fn thaw_account() -> Weight;
+	fn transfer_batch(n: u32, ) -> Weight;
+	fn reap_dust_accounts(n: u32, ) -> Weight;
+	fn set_compliance_policy() -> Weight;
+	fn add_compliance_member() -> Weight;
+	fn remove_compliance_member() -> Weight;
+	fn force_unreserve_named() -> Weight;
+	fn force_repatriate_reserved_named() -> Weight;
+	fn transfer_all_currencies(c: u32, ) -> Weight;
+	fn transfer_with_memo() -> Weight;
+}
+
+/// The reads and writes of a transfer which the weights below predate: the
+/// frozen currency and account, the compliance policy and membership of both
+/// accounts, and the balance checkpoints of both accounts and the total
+/// issuance checkpoint, with the block their history started.
+fn transfer_checks() -> Weight {
+	RocksDbWeight::get().reads(10 as u64)
+		.saturating_add(RocksDbWeight::get().writes(3 as u64))
 }
 
 /// Default weights.
@@ -42,25 +67,125 @@ impl WeightInfo for () {
 		Weight::from_parts(69_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(5 as u64))
 			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+			.saturating_add(transfer_checks())
 	}
 	fn transfer_all() -> Weight {
 		Weight::from_parts(69_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(5 as u64))
 			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+			.saturating_add(transfer_checks())
 	}
 	fn transfer_keep_alive() -> Weight {
 		Weight::from_parts(38_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(transfer_checks())
 	}
 	fn force_transfer() -> Weight {
 		Weight::from_parts(45_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(4 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(transfer_checks())
 	}
 	fn set_balance() -> Weight {
 		Weight::from_parts(34_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+
+	// The weights below are hand-estimated, not produced by the benchmark CLI.
+	// Replace them with generated weights once these calls are benchmarked.
+	fn approve_transfer() -> Weight {
+		Weight::from_parts(36_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn cancel_approval() -> Weight {
+		Weight::from_parts(34_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_approved() -> Weight {
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+			.saturating_add(transfer_checks())
+	}
+	fn freeze_currency() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn thaw_currency() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn freeze_account() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn thaw_account() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn transfer_batch(n: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(69_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(0 as u64))
+			.saturating_add(RocksDbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(0 as u64))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(n as u64)))
+			.saturating_add(transfer_checks().saturating_mul(n as u64))
+	}
+	fn reap_dust_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(35_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(0 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(0 as u64))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
+	fn set_compliance_policy() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn add_compliance_member() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn remove_compliance_member() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn force_unreserve_named() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn force_repatriate_reserved_named() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_all_currencies(c: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(69_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((5 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(0 as u64))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(c as u64)))
+			.saturating_add(transfer_checks().saturating_mul(c as u64))
+	}
+	fn transfer_with_memo() -> Weight {
+		Weight::from_parts(70_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+			.saturating_add(transfer_checks())
+	}
 }
@@ -1,5 +1,5 @@
 use crate::{arithmetic, Happened};
-use frame_support::traits::tokens::Balance;
+use frame_support::traits::{tokens::Balance, ExistenceRequirement, SameOrOther, TryDrop, WithdrawReasons};
 pub use frame_support::{
 	traits::{BalanceStatus, DefensiveSaturating, LockIdentifier},
 	transactional,
@@ -133,10 +133,70 @@ pub trait MultiLockableCurrency<AccountId>: MultiCurrency<AccountId> {
 		amount: Self::Balance,
 	) -> DispatchResult;
 
+	/// Like `set_lock`, but the lock only prevents withdrawals for any of
+	/// `reasons`.
+	///
+	/// The default implementation ignores `reasons` and calls `set_lock`, so
+	/// the lock prevents withdrawals for all reasons.
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::set_lock(lock_id, currency_id, who, amount)
+	}
+
+	/// Like `extend_lock`, but the lock also prevents withdrawals for any of
+	/// `reasons`.
+	///
+	/// The default implementation ignores `reasons` and calls `extend_lock`,
+	/// so the lock prevents withdrawals for all reasons.
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::extend_lock(lock_id, currency_id, who, amount)
+	}
+
 	/// Remove an existing lock.
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &AccountId) -> DispatchResult;
 }
 
+/// A fungible multi-currency system whose locks can expire on their own.
+pub trait MultiExpiringLockableCurrency<AccountId>: MultiLockableCurrency<AccountId> {
+	/// Create a new balance lock on account `who` that stops having any effect
+	/// once `until` is reached.
+	///
+	/// If the lock `lock_id` already exists, this will update it, including
+	/// its expiry.
+	fn set_lock_until(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		until: Self::Moment,
+	) -> DispatchResult;
+
+	/// Changes a balance lock (selected by `lock_id`) so that it becomes less
+	/// liquid in all parameters or creates a new one if it does not exist.
+	///
+	/// `extend_lock_until` will set:
+	/// - maximum `amount`
+	/// - the later expiry, a lock without expiry is never shortened
+	fn extend_lock_until(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		until: Self::Moment,
+	) -> DispatchResult;
+}
+
 /// A fungible multi-currency system where funds can be reserved from the user.
 pub trait MultiReservableCurrency<AccountId>: MultiCurrency<AccountId> {
 	/// Same result as `reserve(who, value)` (but without the side-effects)
@@ -356,6 +416,89 @@ pub trait NamedMultiReservableCurrency<AccountId>: MultiReservableCurrency<Accou
 	}
 }
 
+/// Funds of a single currency which were created or destroyed without an
+/// equal and opposite accounting. Like `Imbalance`, but carrying the currency,
+/// so imbalances of different currencies can't be combined.
+pub trait MultiImbalance<CurrencyId, Balance>: Sized + TryDrop {
+	/// The oppositely imbalanced type.
+	type Opposite: MultiImbalance<CurrencyId, Balance>;
+
+	/// The zero imbalance of `currency_id`.
+	fn zero(currency_id: CurrencyId) -> Self;
+
+	/// The currency of this imbalance.
+	fn currency_id(&self) -> CurrencyId;
+
+	/// The raw value of this imbalance.
+	fn peek(&self) -> Balance;
+
+	/// Consume `self` and return two independent instances of the same
+	/// currency; the first is at most `amount` and the second is the
+	/// remainder.
+	fn split(self, amount: Balance) -> (Self, Self);
+
+	/// Consume `self` and `other` and return the combined imbalance, or both
+	/// if their currencies differ.
+	fn merge(self, other: Self) -> result::Result<Self, (Self, Self)>;
+
+	/// Consume `other` into `self`, or return `other` if their currencies
+	/// differ.
+	fn subsume(&mut self, other: Self) -> result::Result<(), Self>;
+
+	/// Consume `self` and an opposite imbalance and return the net imbalance,
+	/// or both if their currencies differ.
+	fn offset(self, other: Self::Opposite)
+		-> result::Result<SameOrOther<Self, Self::Opposite>, (Self, Self::Opposite)>;
+}
+
+/// A fungible multi-currency system whose funds can be created, destroyed
+/// and moved with imbalances, e.g. to handle the fees of many currencies.
+///
+/// Dropping an imbalance squares up the total issuance of its currency.
+pub trait MultiCurrencyImbalance<AccountId>: MultiCurrency<AccountId> {
+	/// The imbalance of funds which were added to the system.
+	type PositiveImbalance: MultiImbalance<Self::CurrencyId, Self::Balance, Opposite = Self::NegativeImbalance>;
+
+	/// The imbalance of funds which were removed from the system.
+	type NegativeImbalance: MultiImbalance<Self::CurrencyId, Self::Balance, Opposite = Self::PositiveImbalance>;
+
+	/// Increase the total issuance of `currency_id` by `amount` and return
+	/// the funds to be placed somewhere. If they are dropped, the total
+	/// issuance is reduced again.
+	fn issue(currency_id: Self::CurrencyId, amount: Self::Balance) -> Self::NegativeImbalance;
+
+	/// Reduce the total issuance of `currency_id` by `amount` and return the
+	/// funds to be removed from somewhere. If they are dropped, the total
+	/// issuance is increased again.
+	fn burn(currency_id: Self::CurrencyId, amount: Self::Balance) -> Self::PositiveImbalance;
+
+	/// Deposit `value` into the free balance of `who`, creating the account
+	/// if needed. The returned imbalance is zero if the deposit failed.
+	fn deposit_creating(
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::PositiveImbalance;
+
+	/// Remove `value` from the free balance of `who`, respecting
+	/// `liveness`.
+	fn withdraw(
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+		liveness: ExistenceRequirement,
+	) -> result::Result<Self::NegativeImbalance, DispatchError>;
+
+	/// Remove the value of `imbalance` from the free balance of `who` and
+	/// offset it against `imbalance`. Returns `imbalance` if it can't be
+	/// settled.
+	fn settle(
+		who: &AccountId,
+		imbalance: Self::PositiveImbalance,
+		liveness: ExistenceRequirement,
+	) -> result::Result<(), Self::PositiveImbalance>;
+}
+
 /// Abstraction over a fungible (single) currency system.
 pub trait BasicCurrency<AccountId> {
 	/// The balance of an account.
@@ -446,6 +589,34 @@ pub trait BasicLockableCurrency<AccountId>: BasicCurrency<AccountId> {
 	/// - maximum `amount`
 	fn extend_lock(lock_id: LockIdentifier, who: &AccountId, amount: Self::Balance) -> DispatchResult;
 
+	/// Like `set_lock`, but the lock only prevents withdrawals for any of
+	/// `reasons`.
+	///
+	/// The default implementation ignores `reasons` and calls `set_lock`, so
+	/// the lock prevents withdrawals for all reasons.
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::set_lock(lock_id, who, amount)
+	}
+
+	/// Like `extend_lock`, but the lock also prevents withdrawals for any of
+	/// `reasons`.
+	///
+	/// The default implementation ignores `reasons` and calls `extend_lock`,
+	/// so the lock prevents withdrawals for all reasons.
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::extend_lock(lock_id, who, amount)
+	}
+
 	/// Remove an existing lock.
 	fn remove_lock(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult;
 }
@@ -12,8 +12,9 @@ pub use asset_registry::{FixedConversionRateProvider, WeightToFeeConverter};
 pub use auction::{Auction, AuctionHandler, AuctionInfo, OnNewBidResult};
 pub use currency::{
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
-	NamedBasicReservableCurrency, NamedMultiReservableCurrency,
+	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiCurrencyImbalance, MultiExpiringLockableCurrency,
+	MultiImbalance, MultiLockableCurrency, MultiReservableCurrency, NamedBasicReservableCurrency,
+	NamedMultiReservableCurrency,
 };
 pub use data_provider::{DataFeeder, DataProvider, DataProviderExtended};
 pub use get_by_key::GetByKey;
@@ -266,6 +266,7 @@ pub fn para_ext(para_id: u32) -> TestExternalities {
 
 	orml_tokens::GenesisConfig::<Runtime> {
 		balances: vec![(ALICE, CurrencyId::R, 1_000)],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -290,6 +291,7 @@ pub fn para_teleport_ext(para_id: u32) -> TestExternalities {
 
 	orml_tokens::GenesisConfig::<Runtime> {
 		balances: vec![(ALICE, CurrencyId::R, 1_000)],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
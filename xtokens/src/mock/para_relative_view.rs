@@ -83,6 +83,12 @@ parameter_type_with_key! {
 	};
 }
 
+parameter_type_with_key! {
+	pub MaxTotalIssuance: |_currency_id: CurrencyId| -> Option<Balance> {
+		None
+	};
+}
+
 impl orml_tokens::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = Balance;
@@ -95,6 +101,22 @@ impl orml_tokens::Config for Runtime {
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Everything;
+	type RuntimeHoldReason = ();
+	type MaxHolds = ConstU32<1>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ConstU32<1>;
+	type MaxTotalIssuance = MaxTotalIssuance;
+	type ApprovalDeposits = ExistentialDeposits;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxBatchTransfers = ConstU32<10>;
+	type MultiBlockMigrations = ();
+	type MaxReapAccounts = ConstU32<10>;
+	type CheckpointCurrencies = frame_support::traits::Nothing;
+	type MaxCheckpoints = ConstU32<0>;
+	type IsSufficient = frame_support::traits::Everything;
+	type ComplianceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransferAllCurrencies = ConstU32<10>;
+	type MaxMemoLength = ConstU32<32>;
 }
 
 parameter_types! {